@@ -0,0 +1,123 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+type Entry = (Instant, Arc<dyn Any + Send + Sync>);
+
+/// Decoded GET results kept for a fixed time-to-live, keyed by URL and result type.
+///
+/// Every write to a URL bumps its generation; a fetch records the generation it started
+/// under, and its result is not cached if a write to the URL happened in the meantime.
+#[derive(Debug)]
+pub(crate) struct Cache {
+    ttl: Duration,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<(String, TypeId), Entry>,
+    generations: HashMap<String, u64>,
+}
+
+impl Cache {
+    pub(crate) fn new(ttl: Duration) -> Cache {
+        Cache {
+            ttl,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// The `T` cached for `url`, unless it has expired.
+    pub(crate) fn get<T: Clone + 'static>(&self, url: &str) -> Option<T> {
+        let key = (url.to_owned(), TypeId::of::<T>());
+        let entries = &mut self.inner.lock().unwrap().entries;
+        let (stored, value) = entries.get(&key)?;
+        if stored.elapsed() >= self.ttl {
+            entries.remove(&key);
+            return None;
+        }
+        value.downcast_ref::<T>().cloned()
+    }
+
+    /// The write generation of `url`, to be passed to [`Cache::insert`] once a fetch
+    /// started now completes.
+    pub(crate) fn generation(&self, url: &str) -> u64 {
+        let inner = self.inner.lock().unwrap();
+        inner.generations.get(url).copied().unwrap_or(0)
+    }
+
+    /// Caches `value` for `url` unless `url` was written to since `generation`, dropping
+    /// every expired entry so the map only holds live results.
+    pub(crate) fn insert<T: Send + Sync + 'static>(&self, url: &str, generation: u64, value: T) {
+        let key = (url.to_owned(), TypeId::of::<T>());
+        let entry: Entry = (Instant::now(), Arc::new(value));
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generations.get(url).copied().unwrap_or(0) != generation {
+            return;
+        }
+        inner
+            .entries
+            .retain(|_, (stored, _)| stored.elapsed() < self.ttl);
+        inner.entries.insert(key, entry);
+    }
+
+    /// Drops whatever is cached for `url`, whatever its type, and bumps its generation so
+    /// fetches already in flight are not cached either.
+    pub(crate) fn invalidate(&self, url: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries.retain(|(cached, _), _| cached != url);
+        *inner.generations.entry(url.to_owned()).or_default() += 1;
+    }
+
+    pub(crate) fn clear(&self) {
+        self.inner.lock().unwrap().entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_prunes_expired_entries() {
+        let cache = Cache::new(Duration::from_millis(20));
+        for id in 0..100 {
+            cache.insert(&format!("/personality/{id}"), 0, id);
+        }
+        std::thread::sleep(Duration::from_millis(30));
+        cache.insert("/personality/fresh", 0, 0);
+
+        assert_eq!(cache.inner.lock().unwrap().entries.len(), 1);
+        assert_eq!(cache.get::<i32>("/personality/fresh"), Some(0));
+    }
+
+    #[test]
+    fn test_invalidate_drops_every_type() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.insert("/personality/1", 0, 1_i32);
+        cache.insert("/personality/1", 0, "one".to_string());
+        cache.insert("/personality/2", 0, 2_i32);
+
+        cache.invalidate("/personality/1");
+
+        assert_eq!(cache.get::<i32>("/personality/1"), None);
+        assert_eq!(cache.get::<String>("/personality/1"), None);
+        assert_eq!(cache.get::<i32>("/personality/2"), Some(2));
+    }
+
+    #[test]
+    fn test_insert_skips_results_fetched_before_a_write() {
+        let cache = Cache::new(Duration::from_secs(60));
+        let generation = cache.generation("/personality/1");
+        cache.invalidate("/personality/1");
+        cache.insert("/personality/1", generation, 1_i32);
+        assert_eq!(cache.get::<i32>("/personality/1"), None);
+
+        cache.insert("/personality/1", cache.generation("/personality/1"), 2_i32);
+        assert_eq!(cache.get::<i32>("/personality/1"), Some(2));
+    }
+}
@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use crate::SoulgraphError;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Entity {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<uuid::Uuid>,
-    pub form: String,
+    pub form: EntityForm,
     pub occupation: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gender: Option<String>,
@@ -12,15 +18,104 @@ pub struct Entity {
     pub age: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "expertise_format::deserialize"
+    )]
     pub expertise: Option<Vec<String>>,
 }
 
+/// The kind of being an entity is. Unrecognised strings are kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum EntityForm {
+    Ai,
+    Human,
+    Other(String),
+}
+
+impl EntityForm {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EntityForm::Ai => "ai",
+            EntityForm::Human => "human",
+            EntityForm::Other(form) => form,
+        }
+    }
+}
+
+impl From<String> for EntityForm {
+    fn from(form: String) -> Self {
+        match form.as_str() {
+            "ai" => EntityForm::Ai,
+            "human" => EntityForm::Human,
+            _ => EntityForm::Other(form),
+        }
+    }
+}
+
+impl From<&str> for EntityForm {
+    fn from(form: &str) -> Self {
+        EntityForm::from(form.to_string())
+    }
+}
+
+impl From<EntityForm> for String {
+    fn from(form: EntityForm) -> Self {
+        match form {
+            EntityForm::Other(form) => form,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for EntityForm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<&str> for EntityForm {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+mod expertise_format {
+    use serde::{Deserialize, Deserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Expertise {
+        List(Vec<String>),
+        CommaSeparated(String),
+    }
+
+    /// Accepts either a JSON array of strings or a single comma-separated string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(
+            Option::<Expertise>::deserialize(deserializer)?.map(|expertise| match expertise {
+                Expertise::List(list) => list,
+                Expertise::CommaSeparated(list) => list
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(String::from)
+                    .collect(),
+            }),
+        )
+    }
+}
+
 impl Default for Entity {
     fn default() -> Self {
         Self {
             id: None,
-            form: "ai".to_string(),
+            form: EntityForm::Ai,
             occupation: "assistant".to_string(),
             gender: None,
             age: None,
@@ -30,10 +125,86 @@ impl Default for Entity {
     }
 }
 
+impl Entity {
+    /// Returns `false` when `form` is not one of the recognised forms, e.g. a typo.
+    pub fn is_known_form(&self) -> bool {
+        !matches!(self.form, EntityForm::Other(_))
+    }
+
+    /// Trims `expertise` entries, drops blank ones and case-insensitive duplicates (keeping
+    /// the first-seen casing), then sorts case-insensitively.
+    pub fn normalize_expertise(&mut self) {
+        let Some(expertise) = self.expertise.as_mut() else {
+            return;
+        };
+        let mut seen = std::collections::HashSet::new();
+        let mut normalized: Vec<String> = expertise
+            .iter()
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty() && seen.insert(item.to_lowercase()))
+            .map(String::from)
+            .collect();
+        normalized.sort_by_cached_key(|item| item.to_lowercase());
+        *expertise = normalized;
+    }
+
+    /// Describes the entity in a sentence or two for an LLM system prompt, leaving out
+    /// any optional field that is not set.
+    pub fn to_prompt(&self) -> String {
+        format!("You are {}", self.description())
+    }
+
+    /// [`Entity::to_prompt`] without its leading "You are ", e.g. "an AI assistant.".
+    pub(crate) fn description(&self) -> String {
+        let form = match &self.form {
+            EntityForm::Ai => "AI",
+            form => form.as_str(),
+        };
+        let description = format!("{form} {}", self.occupation);
+        let article = match description.chars().next() {
+            Some(c) if "aeiouAEIOU".contains(c) => "an",
+            _ => "a",
+        };
+        let mut prompt = format!("{article} {description}");
+
+        let mut details = Vec::new();
+        if let Some(gender) = &self.gender {
+            details.push(gender.clone());
+        }
+        if let Some(age) = &self.age {
+            details.push(format!("aged {age}"));
+        }
+        if !details.is_empty() {
+            prompt.push_str(&format!(" ({})", details.join(", ")));
+        }
+        prompt.push('.');
+
+        if let Some(background) = &self.background {
+            prompt.push_str(&format!(" Background: {background}."));
+        }
+        if let Some(expertise) = self.expertise.as_ref().filter(|e| !e.is_empty()) {
+            prompt.push_str(&format!(" Expertise: {}.", expertise.join(", ")));
+        }
+        prompt
+    }
+}
+
+impl TryFrom<serde_json::Value> for Entity {
+    type Error = SoulgraphError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Forms accepted by [`EntityBuilder::build`] under [`EntityBuilder::strict_form`].
+pub const KNOWN_FORMS: [&str; 4] = ["ai", "human", "animal", "fictional"];
+
 #[derive(Debug, PartialEq)]
 pub enum EntityBuilderError {
     MissingForm,
     MissingOccupation,
+    UnknownForm(String),
 }
 
 impl std::fmt::Display for EntityBuilderError {
@@ -41,6 +212,11 @@ impl std::fmt::Display for EntityBuilderError {
         match self {
             EntityBuilderError::MissingForm => write!(f, "form is required"),
             EntityBuilderError::MissingOccupation => write!(f, "occupation is required"),
+            EntityBuilderError::UnknownForm(form) => write!(
+                f,
+                "unknown form \"{form}\"; expected one of {}",
+                KNOWN_FORMS.join(", ")
+            ),
         }
     }
 }
@@ -49,12 +225,13 @@ impl std::error::Error for EntityBuilderError {}
 
 #[derive(Default, Debug)]
 pub struct EntityBuilder {
-    form: Option<String>,
+    form: Option<EntityForm>,
     occupation: Option<String>,
     gender: Option<String>,
     age: Option<String>,
     background: Option<String>,
     expertise: Option<Vec<String>>,
+    strict_form: bool,
 }
 
 impl EntityBuilder {
@@ -62,38 +239,79 @@ impl EntityBuilder {
         Self::default()
     }
 
-    pub fn form(mut self, form: impl Into<String>) -> Self {
+    #[must_use]
+    pub fn form(mut self, form: impl Into<EntityForm>) -> Self {
         self.form = Some(form.into());
         self
     }
 
+    #[must_use]
     pub fn occupation(mut self, occupation: impl Into<String>) -> Self {
         self.occupation = Some(occupation.into());
         self
     }
 
+    #[must_use]
     pub fn gender(mut self, gender: impl Into<String>) -> Self {
         self.gender = Some(gender.into());
         self
     }
 
+    #[must_use]
     pub fn age(mut self, age: impl Into<String>) -> Self {
         self.age = Some(age.into());
         self
     }
 
+    #[must_use]
     pub fn background(mut self, background: impl Into<String>) -> Self {
         self.background = Some(background.into());
         self
     }
 
+    #[must_use]
     pub fn expertise(mut self, expertise: Vec<String>) -> Self {
         self.expertise = Some(expertise);
         self
     }
 
+    #[must_use]
+    pub fn clear_gender(mut self) -> Self {
+        self.gender = None;
+        self
+    }
+
+    #[must_use]
+    pub fn clear_age(mut self) -> Self {
+        self.age = None;
+        self
+    }
+
+    #[must_use]
+    pub fn clear_background(mut self) -> Self {
+        self.background = None;
+        self
+    }
+
+    #[must_use]
+    pub fn clear_expertise(mut self) -> Self {
+        self.expertise = None;
+        self
+    }
+
+    /// Makes `build` reject forms outside [`KNOWN_FORMS`] to catch typos; any form is
+    /// accepted by default.
+    #[must_use]
+    pub fn strict_form(mut self, strict: bool) -> Self {
+        self.strict_form = strict;
+        self
+    }
+
     pub fn build(self) -> Result<Entity, EntityBuilderError> {
         let form = self.form.ok_or(EntityBuilderError::MissingForm)?;
+        if self.strict_form && !KNOWN_FORMS.contains(&form.as_str()) {
+            return Err(EntityBuilderError::UnknownForm(form.into()));
+        }
         let occupation = self
             .occupation
             .ok_or(EntityBuilderError::MissingOccupation)?;
@@ -113,6 +331,7 @@ impl EntityBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_entity_default() {
@@ -125,6 +344,94 @@ mod tests {
         assert!(entity.expertise.is_none());
     }
 
+    #[test]
+    fn test_entity_try_from_value() {
+        let entity =
+            Entity::try_from(json!({ "form": "human", "occupation": "developer" })).unwrap();
+        assert_eq!(entity.form, "human");
+        assert_eq!(entity.occupation, "developer");
+
+        let result = Entity::try_from(json!({ "form": "human" }));
+        assert!(matches!(result, Err(SoulgraphError::Decode(_))));
+    }
+
+    #[test]
+    fn test_entity_form_round_trip() {
+        let known: Entity =
+            serde_json::from_value(json!({ "form": "human", "occupation": "developer" })).unwrap();
+        assert_eq!(known.form, EntityForm::Human);
+        assert!(known.is_known_form());
+        assert_eq!(serde_json::to_value(&known).unwrap()["form"], "human");
+
+        let typo: Entity =
+            serde_json::from_value(json!({ "form": "humn", "occupation": "developer" })).unwrap();
+        assert_eq!(typo.form, EntityForm::Other("humn".to_string()));
+        assert!(!typo.is_known_form());
+        assert_eq!(serde_json::to_value(&typo).unwrap()["form"], "humn");
+
+        assert!(Entity::default().is_known_form());
+        assert_eq!(EntityForm::Ai.to_string(), "ai");
+    }
+
+    #[test]
+    fn test_expertise_deserialization_forms() {
+        let from_array: Entity = serde_json::from_value(json!({
+            "form": "human",
+            "occupation": "trader",
+            "expertise": ["market psychology", "trading"]
+        }))
+        .unwrap();
+        let from_string: Entity = serde_json::from_value(json!({
+            "form": "human",
+            "occupation": "trader",
+            "expertise": "market psychology, trading"
+        }))
+        .unwrap();
+        let expected = vec!["market psychology".to_string(), "trading".to_string()];
+
+        assert_eq!(from_array.expertise, Some(expected.clone()));
+        assert_eq!(from_string.expertise, Some(expected));
+        assert_eq!(
+            serde_json::to_value(&from_string).unwrap()["expertise"],
+            json!(["market psychology", "trading"])
+        );
+
+        let missing: Entity =
+            serde_json::from_value(json!({ "form": "human", "occupation": "trader" })).unwrap();
+        assert!(missing.expertise.is_none());
+    }
+
+    #[test]
+    fn test_normalize_expertise() {
+        let mut entity = EntityBuilder::new()
+            .form("human")
+            .occupation("trader")
+            .expertise(vec![
+                " Trading".to_string(),
+                "market psychology".to_string(),
+                "trading ".to_string(),
+                "".to_string(),
+                "Market Psychology".to_string(),
+                "Algorithms".to_string(),
+            ])
+            .build()
+            .unwrap();
+
+        entity.normalize_expertise();
+        assert_eq!(
+            entity.expertise,
+            Some(vec![
+                "Algorithms".to_string(),
+                "market psychology".to_string(),
+                "Trading".to_string(),
+            ])
+        );
+
+        let mut without = Entity::default();
+        without.normalize_expertise();
+        assert!(without.expertise.is_none());
+    }
+
     #[test]
     fn test_entity_builder_minimal() {
         let entity = EntityBuilder::new()
@@ -142,6 +449,23 @@ mod tests {
         assert!(entity.expertise.is_none());
     }
 
+    #[test]
+    fn test_entity_builder_strict_form() {
+        let builder = |form: &str| EntityBuilder::new().form(form).occupation("developer");
+
+        let err = builder("humna").strict_form(true).build().unwrap_err();
+        assert_eq!(err, EntityBuilderError::UnknownForm("humna".to_string()));
+        assert_eq!(
+            err.to_string(),
+            "unknown form \"humna\"; expected one of ai, human, animal, fictional"
+        );
+
+        for form in KNOWN_FORMS {
+            assert!(builder(form).strict_form(true).build().is_ok());
+        }
+        assert_eq!(builder("humna").build().unwrap().form, "humna");
+    }
+
     #[test]
     fn test_entity_builder_full() {
         let expertise = vec!["programming".to_string(), "rust".to_string()];
@@ -168,6 +492,60 @@ mod tests {
         assert_eq!(entity.expertise, Some(expertise));
     }
 
+    #[test]
+    fn test_to_prompt_minimal_entity() {
+        assert_eq!(Entity::default().to_prompt(), "You are an AI assistant.");
+
+        let entity = EntityBuilder::new()
+            .form("human")
+            .occupation("developer")
+            .build()
+            .unwrap();
+        assert_eq!(entity.to_prompt(), "You are a human developer.");
+    }
+
+    #[test]
+    fn test_to_prompt_full_entity() {
+        let entity = EntityBuilder::new()
+            .form("human")
+            .occupation("developer")
+            .gender("female")
+            .age("30")
+            .background("Computer Science graduate")
+            .expertise(vec!["programming".to_string(), "rust".to_string()])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            entity.to_prompt(),
+            "You are a human developer (female, aged 30). \
+             Background: Computer Science graduate. \
+             Expertise: programming, rust."
+        );
+    }
+
+    #[test]
+    fn test_entity_builder_clear_optional_fields() {
+        let entity = EntityBuilder::new()
+            .form("human")
+            .occupation("developer")
+            .gender("female")
+            .age("30")
+            .background("Computer Science graduate")
+            .expertise(vec!["rust".to_string()])
+            .clear_gender()
+            .clear_age()
+            .clear_background()
+            .clear_expertise()
+            .build()
+            .unwrap();
+
+        assert!(entity.gender.is_none());
+        assert!(entity.age.is_none());
+        assert!(entity.background.is_none());
+        assert!(entity.expertise.is_none());
+    }
+
     #[test]
     fn test_entity_builder_missing_required_fields() {
         let result = EntityBuilder::new().build();
@@ -0,0 +1,119 @@
+use std::{error, fmt};
+
+use reqwest::StatusCode;
+
+use crate::PreparedRequest;
+
+#[derive(Debug)]
+pub enum SoulgraphError {
+    /// The request could not be sent or the response could not be read.
+    Http(reqwest::Error),
+    /// The server answered with a non-success status.
+    Status { status: StatusCode, body: String },
+    /// A payload could not be decoded into the expected type.
+    Decode(serde_json::Error),
+    /// An id was not a valid UUID.
+    InvalidId(uuid::Error),
+    /// The request was rejected client-side before being sent.
+    InvalidInput(String),
+    /// Dry-run mode is enabled; carries the request that would have been sent.
+    DryRun(PreparedRequest),
+    /// A local file could not be read or written.
+    Io(std::io::Error),
+    /// A GraphQL query was answered with an `errors` array; carries each error's message.
+    GraphQl(Vec<String>),
+}
+
+impl fmt::Display for SoulgraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SoulgraphError::Http(err) => write!(f, "request failed: {err}"),
+            SoulgraphError::Status { status, body } => {
+                write!(f, "server responded with {status}: {body}")
+            }
+            SoulgraphError::Decode(err) => write!(f, "failed to decode payload: {err}"),
+            SoulgraphError::InvalidId(err) => write!(f, "invalid id: {err}"),
+            SoulgraphError::InvalidInput(reason) => write!(f, "invalid input: {reason}"),
+            SoulgraphError::DryRun(request) => {
+                write!(f, "dry run: {} {}", request.method, request.url)
+            }
+            SoulgraphError::Io(err) => write!(f, "i/o error: {err}"),
+            SoulgraphError::GraphQl(messages) => {
+                write!(f, "graphql errors: {}", messages.join("; "))
+            }
+        }
+    }
+}
+
+impl error::Error for SoulgraphError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SoulgraphError::Http(err) => Some(err),
+            SoulgraphError::Status { .. } => None,
+            SoulgraphError::Decode(err) => Some(err),
+            SoulgraphError::InvalidId(err) => Some(err),
+            SoulgraphError::InvalidInput(_) => None,
+            SoulgraphError::DryRun(_) => None,
+            SoulgraphError::Io(err) => Some(err),
+            SoulgraphError::GraphQl(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for SoulgraphError {
+    fn from(err: reqwest::Error) -> Self {
+        SoulgraphError::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for SoulgraphError {
+    fn from(err: serde_json::Error) -> Self {
+        SoulgraphError::Decode(err)
+    }
+}
+
+impl From<std::io::Error> for SoulgraphError {
+    fn from(err: std::io::Error) -> Self {
+        SoulgraphError::Io(err)
+    }
+}
+
+impl From<uuid::Error> for SoulgraphError {
+    fn from(err: uuid::Error) -> Self {
+        SoulgraphError::InvalidId(err)
+    }
+}
+
+/// Why [`Soulgraph::ping_auth`](crate::Soulgraph::ping_auth) failed.
+#[derive(Debug)]
+pub enum AuthError {
+    /// The server rejected the API key (401).
+    Unauthorized,
+    /// The API key is valid but lacks access (403).
+    Forbidden,
+    /// The server could not be reached, e.g. a bad URL, DNS failure or timeout.
+    Unreachable(reqwest::Error),
+    /// Any other failure, such as an unexpected status.
+    Other(SoulgraphError),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuthError::Unauthorized => write!(f, "api key was rejected"),
+            AuthError::Forbidden => write!(f, "api key is not allowed to access this server"),
+            AuthError::Unreachable(err) => write!(f, "server is unreachable: {err}"),
+            AuthError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl error::Error for AuthError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AuthError::Unauthorized | AuthError::Forbidden => None,
+            AuthError::Unreachable(err) => Some(err),
+            AuthError::Other(err) => Some(err),
+        }
+    }
+}
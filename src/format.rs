@@ -0,0 +1,100 @@
+use serde::de::DeserializeOwned;
+
+use crate::SoulgraphError;
+
+/// Response format requested through the `Accept` header.
+///
+/// Request bodies are always sent as JSON; only responses are negotiated. Which variants
+/// exist depends on the `yaml` and `msgpack` features, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Format {
+    #[default]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl Format {
+    /// The media type sent in the `Accept` header.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            #[cfg(feature = "yaml")]
+            Format::Yaml => "application/yaml",
+            #[cfg(feature = "msgpack")]
+            Format::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Picks the format matching a response `Content-Type`, falling back to JSON.
+    pub(crate) fn from_content_type(content_type: Option<&str>) -> Format {
+        match content_type {
+            #[cfg(feature = "yaml")]
+            Some(value) if value.contains("yaml") => Format::Yaml,
+            #[cfg(feature = "msgpack")]
+            Some(value) if value.contains("msgpack") => Format::MessagePack,
+            _ => Format::Json,
+        }
+    }
+
+    /// Decodes `body` in this format. Non-JSON decode failures are reported as
+    /// `SoulgraphError::Decode` carrying the original message.
+    pub(crate) fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, SoulgraphError> {
+        match self {
+            Format::Json => Ok(serde_json::from_slice(body)?),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_yaml::from_slice(body).map_err(decode_error),
+            #[cfg(feature = "msgpack")]
+            Format::MessagePack => rmp_serde::from_slice(body).map_err(decode_error),
+        }
+    }
+}
+
+#[cfg(any(feature = "yaml", feature = "msgpack"))]
+fn decode_error(err: impl std::fmt::Display) -> SoulgraphError {
+    SoulgraphError::Decode(<serde_json::Error as serde::de::Error>::custom(err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_content_type() {
+        assert_eq!(Format::from_content_type(None), Format::Json);
+        assert_eq!(
+            Format::from_content_type(Some("application/json; charset=utf-8")),
+            Format::Json
+        );
+        #[cfg(feature = "yaml")]
+        assert_eq!(
+            Format::from_content_type(Some("application/x-yaml")),
+            Format::Yaml
+        );
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            Format::from_content_type(Some("application/msgpack")),
+            Format::MessagePack
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_decode() {
+        let soul: crate::Soul = Format::Yaml
+            .decode(
+                serde_yaml::to_string(&crate::Soul::default())
+                    .unwrap()
+                    .as_bytes(),
+            )
+            .unwrap();
+        assert_eq!(soul, crate::Soul::default());
+        assert!(matches!(
+            Format::Yaml.decode::<crate::Soul>(b"- not a soul"),
+            Err(SoulgraphError::Decode(_))
+        ));
+    }
+}
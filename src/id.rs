@@ -0,0 +1,67 @@
+//! Lenient deserialization of optional entity ids.
+//!
+//! Some servers wrap ids Mongo-style as `{"$oid": "..."}` or as `{"id": "..."}` instead
+//! of sending a bare UUID string. Ids are always serialized back as a plain string.
+
+use serde::{Deserialize, Deserializer};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Id {
+    Bare(Uuid),
+    Wrapped {
+        #[serde(rename = "$oid", alias = "id")]
+        id: Uuid,
+    },
+}
+
+/// Accepts `null`, a bare UUID string or an object with an `$oid` or `id` key.
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<Uuid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<Id>::deserialize(deserializer)?.map(|id| match id {
+        Id::Bare(id) | Id::Wrapped { id } => id,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{personality::traits::Trait, Soul};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    const ID: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+    #[test]
+    fn test_bare_and_wrapped_ids_deserialize_alike() {
+        let expected = Some(Uuid::parse_str(ID).unwrap());
+        let mut value = serde_json::to_value(Soul::default()).unwrap();
+
+        for id in [json!(ID), json!({ "$oid": ID }), json!({ "id": ID })] {
+            value["id"] = id;
+            let soul: Soul = serde_json::from_value(value.clone()).unwrap();
+            assert_eq!(soul.id, expected);
+            assert_eq!(serde_json::to_value(&soul).unwrap()["id"], json!(ID));
+        }
+    }
+
+    #[test]
+    fn test_missing_null_and_invalid_ids() {
+        let mut value = json!({ "trait": "kind", "strength": 0.5 });
+        assert!(serde_json::from_value::<Trait>(value.clone())
+            .unwrap()
+            .id
+            .is_none());
+
+        value["id"] = json!(null);
+        assert!(serde_json::from_value::<Trait>(value.clone())
+            .unwrap()
+            .id
+            .is_none());
+
+        value["id"] = json!({ "$oid": "not-a-uuid" });
+        assert!(serde_json::from_value::<Trait>(value).is_err());
+    }
+}
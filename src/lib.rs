@@ -1,27 +1,168 @@
+mod cache;
 pub mod entity;
+pub mod error;
+mod format;
+mod id;
 pub mod memories;
+pub mod middleware;
+#[cfg(feature = "multipart")]
+mod multipart;
+mod number;
 pub mod personality;
+pub mod precision;
+mod rate_limit;
+mod response;
+mod single_flight;
 pub mod soul;
+pub mod store;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
 
+use cache::Cache;
+pub use error::{AuthError, SoulgraphError};
+pub use format::Format;
+pub use middleware::RequestMiddleware;
+#[cfg(feature = "multipart")]
+pub use multipart::MultipartPart;
+pub use rate_limit::RateLimit;
 use reqwest::{
-    header::{self, ACCEPT, CONTENT_TYPE},
-    Error, Response,
+    header::{self, HeaderMap, ACCEPT, CONTENT_TYPE},
+    Method, Response, StatusCode,
 };
+pub use response::DeleteResult;
+use single_flight::SingleFlight;
 pub use soul::Soul;
-use std::fmt::format;
+use std::{
+    fmt::{self, format},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+pub use store::DynSoulStore;
+#[cfg(feature = "webhooks")]
+pub use webhooks::verify_webhook_signature;
+
+use serde::{de::DeserializeOwned, Serialize};
 
-use serde::Serialize;
+#[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+compile_error!(
+    "features `native-tls` and `rustls-tls` are mutually exclusive; \
+     use `default-features = false` when enabling `rustls-tls`"
+);
+
+const API_KEY_HEADER: &str = "X-API-KEY";
+/// Request timeout applied unless the builder overrides it.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Wait before the first retry unless the builder overrides it; doubled on each retry.
+#[cfg(feature = "retry")]
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+#[cfg(feature = "tracing")]
+const DEFAULT_MAX_LOGGED_BODY_LEN: usize = 4096;
 
-#[derive(Debug)]
 pub struct Soulgraph {
     client: reqwest::Client,
     base_url: String,
+    headers: HeaderMap,
+    default_query: Vec<(String, String)>,
+    dry_run: bool,
+    #[cfg(feature = "tracing")]
+    log_bodies: bool,
+    #[cfg(feature = "tracing")]
+    max_logged_body_len: usize,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    timeout: Option<Duration>,
+    middleware: Vec<Arc<dyn RequestMiddleware>>,
+    on_request_complete: Option<RequestCallback>,
+    single_flight: Option<SingleFlight>,
+    envelope: bool,
+    cache: Option<Cache>,
+    float_decimals: u32,
+    #[cfg(feature = "retry")]
+    max_retries: u32,
+    #[cfg(feature = "retry")]
+    retry_backoff: Duration,
+}
+
+/// Called after each request with its endpoint, response status and elapsed time; see
+/// [`SoulgraphBuilder::on_request_complete`].
+pub type RequestCallback = Arc<dyn Fn(&str, StatusCode, Duration) + Send + Sync>;
+
+/// A request captured in dry-run mode instead of being sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedRequest {
+    pub method: Method,
+    pub url: String,
+    /// Default headers, with the API key redacted.
+    pub headers: Vec<(String, String)>,
+    /// Serialized JSON body, if any.
+    pub body: Option<String>,
+}
+
+/// Per-request overrides accepted by the `*_with` request methods.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestOptions {
+    /// Replaces the client's timeout for this request only.
+    pub timeout: Option<Duration>,
+    /// Leaves the `X-API-KEY` header off, e.g. for a third-party pre-signed URL.
+    pub omit_api_key: bool,
+    /// Marks a response whose body will be read incrementally, so it is never buffered for
+    /// [`SoulgraphBuilder::single_flight`].
+    pub streaming: bool,
+}
+
+impl RequestOptions {
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> RequestOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    pub fn without_api_key(mut self) -> RequestOptions {
+        self.omit_api_key = true;
+        self
+    }
+
+    #[must_use]
+    pub fn streaming(mut self) -> RequestOptions {
+        self.streaming = true;
+        self
+    }
 }
 
-#[derive(Default)]
+/// Builder for a [`Soulgraph`] client.
+///
+/// Setters consume the builder and return it, so their result must be used:
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// let builder = soulgraph::Soulgraph::builder();
+/// builder.api_key("key");
+/// ```
 pub struct SoulgraphBuilder {
     key: Option<String>,
     url: Option<String>,
+    timeout: Option<Duration>,
+    default_query: Vec<(String, String)>,
+    extra_headers: Vec<(String, String)>,
+    dry_run: bool,
+    accept: Format,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    danger_accept_invalid_certs: bool,
+    middleware: Vec<Arc<dyn RequestMiddleware>>,
+    on_request_complete: Option<RequestCallback>,
+    single_flight: bool,
+    envelope: bool,
+    cache_ttl: Option<Duration>,
+    float_decimals: u32,
+    #[cfg(feature = "retry")]
+    max_retries: u32,
+    #[cfg(feature = "retry")]
+    retry_backoff: Duration,
+    #[cfg(feature = "tracing")]
+    log_bodies: bool,
+    #[cfg(feature = "tracing")]
+    max_logged_body_len: Option<usize>,
 }
 
 impl SoulgraphBuilder {
@@ -29,25 +170,344 @@ impl SoulgraphBuilder {
         SoulgraphBuilder {
             key: None,
             url: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            default_query: Vec::new(),
+            extra_headers: Vec::new(),
+            dry_run: false,
+            accept: Format::Json,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            danger_accept_invalid_certs: false,
+            middleware: Vec::new(),
+            on_request_complete: None,
+            single_flight: false,
+            envelope: false,
+            cache_ttl: None,
+            float_decimals: precision::DEFAULT_DECIMALS,
+            #[cfg(feature = "retry")]
+            max_retries: 0,
+            #[cfg(feature = "retry")]
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            #[cfg(feature = "tracing")]
+            log_bodies: false,
+            #[cfg(feature = "tracing")]
+            max_logged_body_len: None,
         }
     }
 
+    #[must_use]
     pub fn api_key(mut self, key: &str) -> SoulgraphBuilder {
         self.key = Some(key.to_owned());
         self
     }
 
+    #[must_use]
     pub fn base_url(mut self, url: &str) -> SoulgraphBuilder {
         self.url = Some(url.to_owned());
         self
     }
 
+    /// Overrides the request timeout (default [`DEFAULT_TIMEOUT`]).
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> SoulgraphBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Lets requests wait indefinitely for the server.
+    #[must_use]
+    pub fn no_timeout(mut self) -> SoulgraphBuilder {
+        self.timeout = None;
+        self
+    }
+
+    /// Appends `key=value` to the query string of every request, e.g. for tenant scoping.
+    #[must_use]
+    pub fn default_query(mut self, key: &str, value: &str) -> SoulgraphBuilder {
+        self.default_query.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Sends the static header `name: value` with every request, e.g. an `X-Org-ID` a
+    /// gateway requires. It cannot replace the API key, `Content-Type` or `Accept` headers.
+    /// Like the API key, the value is treated as a secret: it is redacted from `Debug` and
+    /// dry-run output and not sent to absolute URLs on another origin.
+    ///
+    /// `build` panics if `name` or `value` is not a valid header name or value.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> SoulgraphBuilder {
+        self.extra_headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// When enabled, requests are returned as `SoulgraphError::DryRun` instead of being sent.
+    #[must_use]
+    pub fn dry_run(mut self, dry_run: bool) -> SoulgraphBuilder {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Asks the server to respond in `format` (default [`Format::Json`]).
+    ///
+    /// Typed getters decode according to the response's `Content-Type`, so a server that
+    /// ignores the preference and answers with JSON is still understood.
+    #[must_use]
+    pub fn accept(mut self, format: Format) -> SoulgraphBuilder {
+        self.accept = format;
+        self
+    }
+
+    /// Caps the idle connections kept open per host (reqwest's default is unbounded).
+    ///
+    /// A higher cap lets bursts of concurrent requests reuse warm connections instead of
+    /// paying for new TCP/TLS handshakes, at the cost of holding more sockets open.
+    #[must_use]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> SoulgraphBuilder {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Closes pooled connections after they sit idle for `timeout` (reqwest's default is 90s).
+    ///
+    /// Longer timeouts favour reuse for steady traffic; shorter ones release sockets sooner
+    /// and avoid reusing connections a server or proxy may already have dropped.
+    #[must_use]
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> SoulgraphBuilder {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Accepts any TLS certificate, including self-signed and expired ones, e.g. for a
+    /// local development server.
+    ///
+    /// # Warning
+    ///
+    /// **For development only.** With this enabled the client no longer verifies who it is
+    /// talking to, so anyone able to intercept traffic can read and alter every request,
+    /// including the API key. Never enable it against a production server.
+    #[must_use]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> SoulgraphBuilder {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Registers `middleware` to run around every request, after any registered earlier.
+    #[must_use]
+    pub fn middleware(mut self, middleware: Arc<dyn RequestMiddleware>) -> SoulgraphBuilder {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Calls `callback` after every request that gets a response, with the endpoint (the
+    /// full URL for `*_absolute` calls), the status and the time until the response headers
+    /// arrived. Useful for feeding metrics without writing a [`RequestMiddleware`].
+    #[must_use]
+    pub fn on_request_complete(mut self, callback: RequestCallback) -> SoulgraphBuilder {
+        self.on_request_complete = Some(callback);
+        self
+    }
+
+    /// Coalesces concurrent GETs of the same URL so only one reaches the server and the
+    /// others receive a copy of its response. Middleware, the completion callback and
+    /// rate-limit tracking see only the request actually sent.
+    ///
+    /// If the shared request fails, each waiting caller sends its own request instead.
+    /// Shared responses are read into memory, so requests made with
+    /// [`RequestOptions::streaming`], such as `MemoryCollection::stream`, are never shared.
+    #[must_use]
+    pub fn single_flight(mut self, enabled: bool) -> SoulgraphBuilder {
+        self.single_flight = enabled;
+        self
+    }
+
+    /// Expects payloads wrapped as `{ "data": ..., "meta": ... }`: typed getters and delete
+    /// confirmations decode the `data` field, and [`Soulgraph::get_with_meta`] also returns
+    /// each response's `meta`. `MemoryCollection::stream` still reads a bare array.
+    #[must_use]
+    pub fn envelope(mut self, enabled: bool) -> SoulgraphBuilder {
+        self.envelope = enabled;
+        self
+    }
+
+    /// Caches the decoded results of the typed getters (`Soul::get`, `Personality::get`,
+    /// `Memory::get`) in memory for `ttl`, keyed by URL, and serves repeats from there
+    /// without a request. A POST, PUT, PATCH or DELETE this client sends to the same
+    /// endpoint evicts it, and a read that overlaps such a write is not cached; changes made
+    /// elsewhere show up once the entry expires or after
+    /// [`Soulgraph::clear_cache`].
+    #[must_use]
+    pub fn cache_ttl(mut self, ttl: Duration) -> SoulgraphBuilder {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Rounds `f32` scores such as trait strengths to `decimals` places in request bodies
+    /// (default [`precision::DEFAULT_DECIMALS`]). Other serialization is unaffected.
+    #[must_use]
+    pub fn float_decimals(mut self, decimals: u32) -> SoulgraphBuilder {
+        self.float_decimals = decimals;
+        self
+    }
+
+    /// Resends a GET, HEAD, PUT or DELETE up to `retries` more times when it fails to get a
+    /// response or gets a 429 or 5xx, waiting [`retry_backoff`](Self::retry_backoff) before
+    /// the first retry and twice as long before each one after. Off (0) by default.
+    ///
+    /// POST and PATCH are never retried, since repeating them can duplicate writes, and
+    /// neither are requests with streaming bodies. With the `tracing` feature each retry
+    /// logs a `warn` event.
+    #[cfg(feature = "retry")]
+    #[must_use]
+    pub fn max_retries(mut self, retries: u32) -> SoulgraphBuilder {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Sets the wait before the first retry (default 200ms); see
+    /// [`max_retries`](Self::max_retries).
+    #[cfg(feature = "retry")]
+    #[must_use]
+    pub fn retry_backoff(mut self, backoff: Duration) -> SoulgraphBuilder {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Logs pretty-printed POST/PUT/PATCH bodies at `debug` level before sending.
+    ///
+    /// Headers, and therefore the API key, are never logged.
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn log_bodies(mut self, log_bodies: bool) -> SoulgraphBuilder {
+        self.log_bodies = log_bodies;
+        self
+    }
+
+    /// Truncates logged bodies to `len` bytes (default 4096).
+    #[cfg(feature = "tracing")]
+    #[must_use]
+    pub fn max_logged_body_len(mut self, len: usize) -> SoulgraphBuilder {
+        self.max_logged_body_len = Some(len);
+        self
+    }
+
+    #[must_use]
     pub fn build(self) -> Soulgraph {
-        match (self.key, self.url) {
-            (Some(key), Some(url)) => Soulgraph::new(key.as_str(), url.as_str()),
+        let (key, url) = match (self.key, self.url) {
+            (Some(key), Some(url)) => (key, url),
             (None, _) => panic!("Missing api key"),
             (_, None) => panic!("Missing base url"),
+        };
+
+        let mut headers = header::HeaderMap::new();
+        for (name, value) in &self.extra_headers {
+            let name = header::HeaderName::from_bytes(name.as_bytes())
+                .unwrap_or_else(|err| panic!("Invalid header name {name:?}: {err}"));
+            let mut value = header::HeaderValue::from_str(value)
+                .unwrap_or_else(|err| panic!("Invalid value for header {name}: {err}"));
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let mut key: header::HeaderValue = key.parse().unwrap();
+        key.set_sensitive(true);
+        headers.insert(API_KEY_HEADER, key);
+        headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        headers.insert(ACCEPT, self.accept.mime_type().parse().unwrap());
+
+        // The API key and extra headers are attached per request so they can be omitted for
+        // third-party URLs.
+        let mut client_headers = HeaderMap::new();
+        for name in [CONTENT_TYPE, ACCEPT] {
+            client_headers.insert(&name, headers[&name].clone());
+        }
+        let mut builder = reqwest::Client::builder().default_headers(client_headers);
+        #[cfg(feature = "native-tls")]
+        {
+            builder = builder.use_native_tls();
+        }
+        #[cfg(feature = "rustls-tls")]
+        {
+            builder = builder.use_rustls_tls();
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
         }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if self.danger_accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        let client = builder.build().expect("Failed to construct http client");
+
+        Soulgraph {
+            client,
+            base_url: url,
+            headers,
+            default_query: self.default_query,
+            dry_run: self.dry_run,
+            #[cfg(feature = "tracing")]
+            log_bodies: self.log_bodies,
+            #[cfg(feature = "tracing")]
+            max_logged_body_len: self
+                .max_logged_body_len
+                .unwrap_or(DEFAULT_MAX_LOGGED_BODY_LEN),
+            rate_limit: Arc::default(),
+            timeout: self.timeout,
+            middleware: self.middleware,
+            on_request_complete: self.on_request_complete,
+            single_flight: self.single_flight.then(SingleFlight::default),
+            envelope: self.envelope,
+            cache: self.cache_ttl.map(Cache::new),
+            float_decimals: self.float_decimals,
+            #[cfg(feature = "retry")]
+            max_retries: self.max_retries,
+            #[cfg(feature = "retry")]
+            retry_backoff: self.retry_backoff,
+        }
+    }
+}
+
+/// Redacts the API key and extra header values.
+impl fmt::Debug for SoulgraphBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoulgraphBuilder")
+            .field("key", &self.key.as_ref().map(|_| "***"))
+            .field("url", &self.url)
+            .field("timeout", &self.timeout)
+            .field("default_query", &self.default_query)
+            .field(
+                "extra_headers",
+                &self
+                    .extra_headers
+                    .iter()
+                    .map(|(name, _)| (name, "***"))
+                    .collect::<Vec<_>>(),
+            )
+            .field("dry_run", &self.dry_run)
+            .field("accept", &self.accept)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field(
+                "danger_accept_invalid_certs",
+                &self.danger_accept_invalid_certs,
+            )
+            .field("middleware", &self.middleware)
+            .field("on_request_complete", &self.on_request_complete.is_some())
+            .field("single_flight", &self.single_flight)
+            .field("envelope", &self.envelope)
+            .field("cache_ttl", &self.cache_ttl)
+            .field("float_decimals", &self.float_decimals)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for SoulgraphBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -57,50 +517,532 @@ impl Soulgraph {
     }
 
     pub fn new(api_key: &str, base_url: &str) -> Soulgraph {
-        let mut headers = header::HeaderMap::new();
-        headers.insert("X-API-KEY", api_key.parse().unwrap());
-        headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
-        headers.insert(ACCEPT, "application/json".parse().unwrap());
-
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
+        Soulgraph::builder()
+            .api_key(api_key)
+            .base_url(base_url)
             .build()
-            .expect("Failed to construct http client");
+    }
 
-        Soulgraph {
-            client,
-            base_url: base_url.to_owned(),
+    /// The request timeout in effect, or `None` when requests may wait indefinitely.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The most recent rate-limit state reported by the server, if any.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// GETs `endpoint` and decodes it into `T` along with the `meta` field of its envelope,
+    /// which is `None` unless [`SoulgraphBuilder::envelope`] is enabled and the server sent
+    /// one.
+    pub async fn get_with_meta<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<(T, Option<serde_json::Value>), SoulgraphError> {
+        response::decode_with_meta(self.get(endpoint).await?).await
+    }
+
+    /// Drops every result cached under [`SoulgraphBuilder::cache_ttl`].
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
         }
     }
 
-    /// Performs a GET request to the specified endpoint
-    pub async fn get(self, endpoint: &str) -> Result<Response, Error> {
+    /// GETs `endpoint` and decodes it into `T`, going through the result cache when
+    /// [`SoulgraphBuilder::cache_ttl`] is set.
+    pub(crate) async fn get_cached<T>(&self, endpoint: &str) -> Result<T, SoulgraphError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let Some(cache) = &self.cache else {
+            return response::decode(self.get(endpoint).await?).await;
+        };
         let url = format_url(self.base_url.as_str(), endpoint);
-        self.client.get(url).send().await
+        if let Some(value) = cache.get(&url) {
+            return Ok(value);
+        }
+        let generation = cache.generation(&url);
+        let value: T = response::decode(self.get(endpoint).await?).await?;
+        cache.insert(&url, generation, value.clone());
+        Ok(value)
+    }
+
+    /// Checks the API key against `/auth/whoami`, telling a rejected key apart from an
+    /// unreachable server.
+    pub async fn ping_auth(&self) -> Result<(), AuthError> {
+        let response = match self.get("/auth/whoami").await {
+            Ok(response) => response,
+            Err(SoulgraphError::Http(err)) => return Err(AuthError::Unreachable(err)),
+            Err(err) => return Err(AuthError::Other(err)),
+        };
+        match response.status() {
+            StatusCode::UNAUTHORIZED => Err(AuthError::Unauthorized),
+            StatusCode::FORBIDDEN => Err(AuthError::Forbidden),
+            _ => response::check_status(response)
+                .await
+                .map(drop)
+                .map_err(AuthError::Other),
+        }
+    }
+
+    /// Runs a GraphQL `query` against `/graphql` and decodes its `data` field into `T`.
+    ///
+    /// A response carrying an `errors` array fails with `SoulgraphError::GraphQl`.
+    pub async fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, SoulgraphError> {
+        let body = serde_json::json!({ "query": query, "variables": variables });
+        let response = self.post("/graphql", &body).await?;
+        let response: response::GraphQlResponse = response::decode_bare(response).await?;
+        if !response.errors.is_empty() {
+            return Err(SoulgraphError::GraphQl(
+                response.errors.into_iter().map(|err| err.message).collect(),
+            ));
+        }
+        Ok(serde_json::from_value(response.data)?)
+    }
+
+    /// Performs a GET request to the specified endpoint
+    pub async fn get(&self, endpoint: impl AsRef<str>) -> Result<Response, SoulgraphError> {
+        self.get_with(endpoint, RequestOptions::default()).await
+    }
+
+    /// Performs a GET request to the specified endpoint with per-request `options`
+    pub async fn get_with(
+        &self,
+        endpoint: impl AsRef<str>,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::GET, endpoint.as_ref(), false, None::<&()>, options)
+            .await
+    }
+
+    /// Performs a HEAD request to the specified endpoint
+    pub async fn head(&self, endpoint: impl AsRef<str>) -> Result<Response, SoulgraphError> {
+        self.head_with(endpoint, RequestOptions::default()).await
+    }
+
+    /// Performs a HEAD request to the specified endpoint with per-request `options`
+    pub async fn head_with(
+        &self,
+        endpoint: impl AsRef<str>,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::HEAD, endpoint.as_ref(), false, None::<&()>, options)
+            .await
     }
 
     /// Performs a POST request to the specified endpoint with the given JSON payload
-    pub async fn post<T: Serialize>(self, endpoint: &str, json: &T) -> Result<Response, Error> {
-        let url = format_url(self.base_url.as_str(), endpoint);
-        self.client.post(url).json(json).send().await
+    pub async fn post<T: Serialize>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &T,
+    ) -> Result<Response, SoulgraphError> {
+        self.post_with(endpoint, json, RequestOptions::default())
+            .await
+    }
+
+    /// Performs a POST request to the specified endpoint with the given JSON payload and
+    /// per-request `options`
+    pub async fn post_with<T: Serialize>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &T,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::POST, endpoint.as_ref(), false, Some(json), options)
+            .await
     }
 
     /// Performs a PUT request to the specified endpoint with the given JSON payload
-    pub async fn put<T: Serialize>(self, endpoint: &str, json: &T) -> Result<Response, Error> {
-        let url = format_url(self.base_url.as_str(), endpoint);
-        self.client.put(url).json(json).send().await
+    pub async fn put<T: Serialize>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &T,
+    ) -> Result<Response, SoulgraphError> {
+        self.put_with(endpoint, json, RequestOptions::default())
+            .await
+    }
+
+    /// Performs a PUT request to the specified endpoint with the given JSON payload and
+    /// per-request `options`
+    pub async fn put_with<T: Serialize>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &T,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::PUT, endpoint.as_ref(), false, Some(json), options)
+            .await
     }
 
     /// Performs a PATCH request to the specified endpoint with the given JSON payload
-    pub async fn patch<T: Serialize>(self, endpoint: &str, json: &T) -> Result<Response, Error> {
-        let url = format_url(self.base_url.as_str(), endpoint);
-        self.client.patch(url).json(json).send().await
+    pub async fn patch<T: Serialize>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &T,
+    ) -> Result<Response, SoulgraphError> {
+        self.patch_with(endpoint, json, RequestOptions::default())
+            .await
+    }
+
+    /// Performs a PATCH request to the specified endpoint with the given JSON payload and
+    /// per-request `options`
+    pub async fn patch_with<T: Serialize>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &T,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::PATCH, endpoint.as_ref(), false, Some(json), options)
+            .await
     }
 
     /// Performs a DELETE request to the specified endpoint with the given JSON payload
-    pub async fn delete(self, endpoint: &str) -> Result<Response, Error> {
-        let url = format_url(self.base_url.as_str(), endpoint);
-        self.client.delete(url).send().await
+    pub async fn delete(&self, endpoint: impl AsRef<str>) -> Result<Response, SoulgraphError> {
+        self.delete_with(endpoint, RequestOptions::default()).await
+    }
+
+    /// Performs a DELETE request to the specified endpoint with per-request `options`
+    pub async fn delete_with(
+        &self,
+        endpoint: impl AsRef<str>,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(
+            Method::DELETE,
+            endpoint.as_ref(),
+            false,
+            None::<&()>,
+            options,
+        )
+        .await
+    }
+
+    /// Performs a GET request to the fully-qualified `url`, without joining it to the base
+    /// URL or appending the default query, e.g. for an upload URL returned by the API.
+    ///
+    /// The API key and extra headers are only sent when `url` has the same origin (scheme,
+    /// host and port) as the base URL, so they never reach another host.
+    pub async fn get_absolute(
+        &self,
+        url: &str,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::GET, url, true, None::<&()>, options)
+            .await
+    }
+
+    /// Performs a POST request with the given JSON payload to the fully-qualified `url`, as
+    /// [`Soulgraph::get_absolute`] does.
+    pub async fn post_absolute<T: Serialize>(
+        &self,
+        url: &str,
+        json: &T,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        self.execute(Method::POST, url, true, Some(json), options)
+            .await
+    }
+
+    /// Performs a `multipart/form-data` POST of `parts` to the specified endpoint, for
+    /// uploading binary attachments. In dry-run mode the prepared request has no `body`.
+    #[cfg(feature = "multipart")]
+    pub async fn post_multipart(
+        &self,
+        endpoint: impl AsRef<str>,
+        parts: Vec<MultipartPart>,
+    ) -> Result<Response, SoulgraphError> {
+        let endpoint = endpoint.as_ref();
+        let options = RequestOptions::default();
+        let request = self
+            .request_builder(Method::POST, endpoint, false, options)
+            .multipart(multipart::form(parts)?)
+            .build()?;
+        if self.dry_run {
+            return Err(self.dry_run_error(&request, None));
+        }
+        self.invalidate_cached(&Method::POST, endpoint);
+        let response = self.send(endpoint, request, options).await;
+        self.invalidate_cached(&Method::POST, endpoint);
+        response
+    }
+
+    /// A request to `endpoint` with the base URL, default query, API key and default headers
+    /// already applied, for customizing with reqwest features this crate does not expose.
+    ///
+    /// The caller sends it themselves, so middleware, the completion callback, dry-run,
+    /// single-flight and rate-limit tracking do not apply.
+    pub fn request(&self, method: Method, endpoint: &str) -> reqwest::RequestBuilder {
+        self.request_builder(method, endpoint, false, RequestOptions::default())
+    }
+
+    async fn execute<T: Serialize>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        absolute: bool,
+        json: Option<&T>,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        let body = json
+            .map(|json| {
+                precision::with_decimals(self.float_decimals, || serde_json::to_string(json))
+            })
+            .transpose()?;
+        let mut request = self.request_builder(method, endpoint, absolute, options);
+        if let Some(body) = &body {
+            request = request.body(body.clone());
+        }
+        let request = request.build()?;
+
+        #[cfg(feature = "tracing")]
+        if let Some(json) = json {
+            self.log_body(request.method(), request.url().as_str(), json);
+        }
+
+        if self.dry_run {
+            return Err(self.dry_run_error(&request, body));
+        }
+        let method = request.method().clone();
+        if !absolute {
+            self.invalidate_cached(&method, endpoint);
+        }
+        let response = self.send(endpoint, request, options).await;
+        if !absolute {
+            self.invalidate_cached(&method, endpoint);
+        }
+        response
+    }
+
+    /// Evicts the cached result for `endpoint` around a write to it: before it is sent, so
+    /// reads stop hitting the old value, and again once its response arrives, so a read
+    /// that raced the write cannot cache what it saw.
+    fn invalidate_cached(&self, method: &Method, endpoint: &str) {
+        if let Some(cache) = &self.cache {
+            if !matches!(*method, Method::GET | Method::HEAD) {
+                cache.invalidate(&format_url(self.base_url.as_str(), endpoint));
+            }
+        }
+    }
+
+    /// Starts a request with everything but the body: URL, default query, API key, extra
+    /// headers and per-request timeout.
+    fn request_builder(
+        &self,
+        method: Method,
+        endpoint: &str,
+        absolute: bool,
+        options: RequestOptions,
+    ) -> reqwest::RequestBuilder {
+        let url = if absolute {
+            endpoint.to_owned()
+        } else {
+            format_url(self.base_url.as_str(), endpoint)
+        };
+        let mut request = self.client.request(method, url);
+        if !absolute && !self.default_query.is_empty() {
+            request = request.query(&self.default_query);
+        }
+        if !absolute || self.is_same_origin(endpoint) {
+            for (name, value) in &self.headers {
+                let skip = *name == CONTENT_TYPE
+                    || *name == ACCEPT
+                    || (options.omit_api_key && name == API_KEY_HEADER);
+                if !skip {
+                    request = request.header(name, value.clone());
+                }
+            }
+        }
+        if let Some(timeout) = options.timeout {
+            request = request.timeout(timeout);
+        }
+        request
+    }
+
+    /// Whether the absolute `url` shares the base URL's scheme, host and port.
+    fn is_same_origin(&self, url: &str) -> bool {
+        match (
+            reqwest::Url::parse(url),
+            reqwest::Url::parse(&self.base_url),
+        ) {
+            (Ok(url), Ok(base_url)) => url.origin() == base_url.origin(),
+            _ => false,
+        }
+    }
+
+    fn dry_run_error(&self, request: &reqwest::Request, body: Option<String>) -> SoulgraphError {
+        SoulgraphError::DryRun(PreparedRequest {
+            method: request.method().clone(),
+            url: request.url().to_string(),
+            headers: self
+                .redacted_headers()
+                .into_iter()
+                .filter(|(name, _)| {
+                    name.eq_ignore_ascii_case(CONTENT_TYPE.as_str())
+                        || name.eq_ignore_ascii_case(ACCEPT.as_str())
+                        || request.headers().contains_key(name.as_str())
+                })
+                .collect(),
+            body,
+        })
+    }
+
+    /// Sends a built request, sharing the response of an identical in-flight GET when
+    /// single-flight is enabled and the response is not streamed, and marks the response
+    /// for envelope decoding.
+    async fn send(
+        &self,
+        endpoint: &str,
+        request: reqwest::Request,
+        options: RequestOptions,
+    ) -> Result<Response, SoulgraphError> {
+        let mut response = match &self.single_flight {
+            Some(single_flight) if request.method() == Method::GET && !options.streaming => {
+                let key = format!("{} {}", request.method(), request.url());
+                single_flight
+                    .run(key, self.send_now(endpoint, request))
+                    .await?
+            }
+            _ => self.send_now(endpoint, request).await?,
+        };
+        if self.envelope {
+            response.extensions_mut().insert(response::Envelope);
+        }
+        Ok(response)
+    }
+
+    /// Sends a built request through the middleware, completion callback and rate-limit
+    /// tracking.
+    async fn send_now(
+        &self,
+        endpoint: &str,
+        mut request: reqwest::Request,
+    ) -> Result<Response, SoulgraphError> {
+        for middleware in &self.middleware {
+            middleware.before(&mut request).await;
+        }
+        let started = Instant::now();
+        #[cfg(feature = "retry")]
+        let response = self.execute_with_retries(endpoint, request).await?;
+        #[cfg(not(feature = "retry"))]
+        let response = self.client.execute(request).await?;
+        if let Some(on_complete) = &self.on_request_complete {
+            on_complete(endpoint, response.status(), started.elapsed());
+        }
+        for middleware in &self.middleware {
+            middleware.after(&response).await;
+        }
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            *self.rate_limit.lock().unwrap() = Some(rate_limit);
+        }
+        Ok(response)
+    }
+
+    /// Executes `request`, resending idempotent ones as configured by
+    /// [`SoulgraphBuilder::max_retries`].
+    #[cfg(feature = "retry")]
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    async fn execute_with_retries(
+        &self,
+        endpoint: &str,
+        mut request: reqwest::Request,
+    ) -> Result<Response, SoulgraphError> {
+        let idempotent = matches!(
+            *request.method(),
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+        );
+        let mut attempt = 0;
+        loop {
+            let retry = request
+                .try_clone()
+                .filter(|_| idempotent && attempt < self.max_retries);
+            let outcome = self.client.execute(request).await;
+            let retryable = match &outcome {
+                Ok(response) => {
+                    response.status().is_server_error()
+                        || response.status() == StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(_) => true,
+            };
+            let Some(next) = retry.filter(|_| retryable) else {
+                return Ok(outcome?);
+            };
+            attempt += 1;
+            let backoff = self
+                .retry_backoff
+                .saturating_mul(1 << (attempt - 1).min(16));
+            #[cfg(feature = "tracing")]
+            match &outcome {
+                Ok(response) => {
+                    tracing::warn!(attempt, endpoint, status = %response.status(), ?backoff, "retrying request")
+                }
+                Err(err) => {
+                    tracing::warn!(attempt, endpoint, error = %err, ?backoff, "retrying request")
+                }
+            }
+            tokio::time::sleep(backoff).await;
+            request = next;
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn log_body<T: Serialize>(&self, method: &Method, url: &str, json: &T) {
+        if !self.log_bodies {
+            return;
+        }
+        let pretty = || serde_json::to_string_pretty(json);
+        let Ok(mut body) = precision::with_decimals(self.float_decimals, pretty) else {
+            return;
+        };
+        if body.len() > self.max_logged_body_len {
+            let mut end = self.max_logged_body_len;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+            body.push_str("...");
+        }
+        tracing::debug!(%method, %url, "request body:\n{body}");
+    }
+
+    fn redacted_headers(&self) -> Vec<(String, String)> {
+        self.headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if value.is_sensitive() {
+                    "***".to_string()
+                } else {
+                    value.to_str().unwrap_or_default().to_string()
+                };
+                (name.to_string(), value)
+            })
+            .collect()
+    }
+}
+
+/// Prints the client's configuration with the API key and extra headers redacted, leaving
+/// out the underlying HTTP client.
+impl fmt::Debug for Soulgraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Soulgraph")
+            .field("base_url", &self.base_url)
+            .field("headers", &self.redacted_headers())
+            .field("default_query", &self.default_query)
+            .field("dry_run", &self.dry_run)
+            .field("timeout", &self.timeout)
+            .field("rate_limit", &self.rate_limit())
+            .field("middleware", &self.middleware)
+            .field("on_request_complete", &self.on_request_complete.is_some())
+            .field("single_flight", &self.single_flight.is_some())
+            .field("envelope", &self.envelope)
+            .field("cache", &self.cache.is_some())
+            .field("float_decimals", &self.float_decimals)
+            .finish_non_exhaustive()
     }
 }
 
@@ -111,6 +1053,302 @@ fn format_url(base_url: &str, endpoint: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::future::BoxFuture;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    async fn ping_auth_with_status(status: u16) -> Result<(), AuthError> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/auth/whoami"))
+            .respond_with(ResponseTemplate::new(status))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Soulgraph::new("test-key", &server.uri()).ping_auth().await
+    }
+
+    #[tokio::test]
+    async fn test_ping_auth_maps_statuses() {
+        assert!(ping_auth_with_status(200).await.is_ok());
+        assert!(matches!(
+            ping_auth_with_status(401).await,
+            Err(AuthError::Unauthorized)
+        ));
+        assert!(matches!(
+            ping_auth_with_status(403).await,
+            Err(AuthError::Forbidden)
+        ));
+        assert!(matches!(
+            ping_auth_with_status(500).await,
+            Err(AuthError::Other(SoulgraphError::Status { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ping_auth_unreachable() {
+        let soulgraph = Soulgraph::new("test-key", "http://127.0.0.1:1");
+        assert!(matches!(
+            soulgraph.ping_auth().await,
+            Err(AuthError::Unreachable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_graphql_extracts_data() {
+        use wiremock::matchers::body_json;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_json(serde_json::json!({
+                "query": "query($id: ID!) { soul(id: $id) { name } }",
+                "variables": { "id": "abc" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "soul": { "name": "Dr. Luna" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let data: serde_json::Value = soulgraph
+            .graphql(
+                "query($id: ID!) { soul(id: $id) { name } }",
+                serde_json::json!({ "id": "abc" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(data["soul"]["name"], "Dr. Luna");
+    }
+
+    #[tokio::test]
+    async fn test_graphql_surfaces_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": null,
+                "errors": [
+                    { "message": "Cannot query field \"nmae\"" },
+                    { "message": "Variable \"$id\" is required" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let err = soulgraph
+            .graphql::<serde_json::Value>("{ soul { nmae } }", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        let SoulgraphError::GraphQl(messages) = err else {
+            panic!("expected graphql errors, got {err:?}");
+        };
+        assert_eq!(
+            messages,
+            vec![
+                "Cannot query field \"nmae\"".to_string(),
+                "Variable \"$id\" is required".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let builder = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url("http://test.com");
+        let builder_debug = format!("{builder:?}");
+        assert!(builder_debug.contains("http://test.com"));
+        assert!(builder_debug.contains("***"));
+        assert!(!builder_debug.contains("secret-key"));
+
+        let soulgraph_debug = format!("{:?}", builder.build());
+        assert!(soulgraph_debug.contains("http://test.com"));
+        assert!(soulgraph_debug.contains("***"));
+        assert!(!soulgraph_debug.contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_absolute_url_requests() {
+        use wiremock::matchers::{header, header_exists};
+
+        let api = MockServer::start().await;
+        let sibling = MockServer::start().await;
+        Mock::given(header_exists("x-api-key"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&sibling)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/uploads/1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&sibling)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/signed"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&sibling)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/uploads/2"))
+            .and(header("x-api-key", "secret-key"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&api)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url(&api.uri())
+            .default_query("tenant", "acme")
+            .build();
+
+        let get = soulgraph
+            .get_absolute(
+                &format!("{}/uploads/1", sibling.uri()),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(get.status(), StatusCode::OK);
+        assert!(get.url().query().is_none());
+
+        let post = soulgraph
+            .post_absolute(
+                &format!("{}/signed", sibling.uri()),
+                &serde_json::json!({ "part": 1 }),
+                RequestOptions::default().without_api_key(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(post.status(), StatusCode::CREATED);
+        assert!(api.received_requests().await.unwrap().is_empty());
+
+        // The key is still sent to absolute URLs on the API's own origin.
+        let own = soulgraph
+            .get_absolute(
+                &format!("{}/uploads/2", api.uri()),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(own.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_absolute_url_requests_omit_extra_headers_for_other_origins() {
+        use wiremock::matchers::{header, header_exists};
+
+        let api = MockServer::start().await;
+        let sibling = MockServer::start().await;
+        Mock::given(header_exists("x-org-id"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&sibling)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/uploads/1"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&sibling)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/uploads/2"))
+            .and(header("x-org-id", "org-42"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&api)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url(&api.uri())
+            .header("X-Org-ID", "org-42")
+            .build();
+
+        let other = soulgraph
+            .get_absolute(
+                &format!("{}/uploads/1", sibling.uri()),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(other.status(), StatusCode::OK);
+
+        let own = soulgraph
+            .get_absolute(
+                &format!("{}/uploads/2", api.uri()),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(own.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_absolute_dry_run_omits_key_for_other_origins() {
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url("https://api.soulgraph.test")
+            .dry_run(true)
+            .build();
+        let has_key = |err: SoulgraphError| {
+            let SoulgraphError::DryRun(request) = err else {
+                panic!("expected a dry-run request, got {err:?}");
+            };
+            request
+                .headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(API_KEY_HEADER))
+        };
+
+        for (url, expected) in [
+            ("https://api.soulgraph.test/uploads/1", true),
+            ("https://uploads.example.com/1", false),
+            ("http://api.soulgraph.test/uploads/1", false),
+            ("https://api.soulgraph.test:8443/uploads/1", false),
+        ] {
+            let err = soulgraph
+                .get_absolute(url, RequestOptions::default())
+                .await
+                .unwrap_err();
+            assert_eq!(has_key(err), expected, "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_on_request_complete_reports_timing() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_delay(Duration::from_millis(20)))
+            .mount(&server)
+            .await;
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .on_request_complete(Arc::new(move |endpoint, status, elapsed| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((endpoint.to_string(), status, elapsed));
+            }))
+            .build();
+
+        soulgraph.get("/personality/1").await.unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        let (endpoint, status, elapsed) = &calls[0];
+        assert_eq!(endpoint, "/personality/1");
+        assert_eq!(*status, StatusCode::NOT_FOUND);
+        assert!(*elapsed >= Duration::from_millis(20));
+    }
 
     #[test]
     fn test_builder_new() {
@@ -141,15 +1379,830 @@ mod tests {
         assert_eq!(soulgraph.base_url, "http://test.com");
     }
 
+    #[test]
+    fn test_client_builds_with_selected_tls_backend() {
+        let soulgraph = Soulgraph::new("test-key", "https://test.com");
+        assert_eq!(soulgraph.base_url, "https://test.com");
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_post_returns_prepared_request() {
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url("http://test.com")
+            .dry_run(true)
+            .build();
+
+        let err = soulgraph
+            .post("/personality", &serde_json::json!({ "name": "Dr. Luna" }))
+            .await
+            .unwrap_err();
+
+        let SoulgraphError::DryRun(request) = err else {
+            panic!("expected a dry-run request, got {err:?}");
+        };
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url, "http://test.com/personality");
+        assert_eq!(request.body.as_deref(), Some(r#"{"name":"Dr. Luna"}"#));
+        assert!(request
+            .headers
+            .contains(&("x-api-key".to_string(), "***".to_string())));
+        assert!(!request
+            .headers
+            .iter()
+            .any(|(_, value)| value.contains("secret-key")));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_get_has_no_body() {
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url("http://test.com")
+            .dry_run(true)
+            .build();
+
+        let err = soulgraph.get("/personality/1").await.unwrap_err();
+        let SoulgraphError::DryRun(request) = err else {
+            panic!("expected a dry-run request, got {err:?}");
+        };
+        assert_eq!(request.method, Method::GET);
+        assert!(request.body.is_none());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    #[cfg(feature = "tracing")]
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn capture_logs() -> (CapturedLogs, tracing::subscriber::DefaultGuard) {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        (logs, tracing::subscriber::set_default(subscriber))
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_log_bodies_emits_debug_event() {
+        let (logs, _guard) = capture_logs();
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url("http://test.com")
+            .dry_run(true)
+            .log_bodies(true)
+            .build();
+        let memory = memories::MemoryBuilder::new("first trade went badly".to_string()).build();
+
+        let _ = soulgraph.post("/memory", &memory).await;
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("DEBUG"));
+        assert!(output.contains("first trade went badly"));
+        assert!(!output.contains("secret-key"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_log_bodies_truncates_large_bodies() {
+        let (logs, _guard) = capture_logs();
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("secret-key")
+            .base_url("http://test.com")
+            .dry_run(true)
+            .log_bodies(true)
+            .max_logged_body_len(32)
+            .build();
+
+        let _ = soulgraph
+            .post("/memory", &serde_json::json!({ "memory": "z".repeat(100) }))
+            .await;
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("..."));
+        assert!(!output.contains(&"z".repeat(40)));
+    }
+
+    #[cfg(feature = "retry")]
+    #[tokio::test]
+    async fn test_max_retries_resends_after_server_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/personality/1"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .max_retries(2)
+            .retry_backoff(Duration::from_millis(1))
+            .build();
+        let response = soulgraph.get("/personality/1").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        // Without retries the 503 comes straight back.
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let response = soulgraph.get("/personality/1").await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[cfg(feature = "retry")]
+    #[tokio::test]
+    async fn test_max_retries_skips_post_and_patch() {
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .max_retries(3)
+            .retry_backoff(Duration::from_millis(1))
+            .build();
+        let body = serde_json::json!({ "name": "once" });
+        let posted = soulgraph.post("/soul", &body).await.unwrap();
+        let patched = soulgraph.patch("/soul/1", &body).await.unwrap();
+        assert_eq!(posted.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(patched.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[cfg(all(feature = "retry", feature = "tracing"))]
+    #[tokio::test]
+    async fn test_retry_logs_warning() {
+        let (logs, _guard) = capture_logs();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .max_retries(1)
+            .retry_backoff(Duration::from_millis(1))
+            .build();
+        soulgraph.get("/personality/1").await.unwrap();
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let warning = output
+            .lines()
+            .find(|line| line.contains("retrying request"))
+            .expect("a retry warning");
+        assert!(warning.contains("WARN"));
+        assert!(warning.contains("attempt=1"));
+        assert!(warning.contains("endpoint=\"/personality/1\""));
+        assert!(warning.contains("status=500"));
+        assert!(warning.contains("backoff=1ms"));
+    }
+
+    #[tokio::test]
+    async fn test_float_decimals_rounds_request_bodies() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let trait_ = personality::traits::TraitBuilder::new("precise")
+            .strength(0.123456)
+            .build()
+            .unwrap();
+
+        let coarse = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .float_decimals(2)
+            .build();
+        let default = Soulgraph::new("test-key", &server.uri());
+        coarse.post("/trait", &trait_).await.unwrap();
+        default.post("/trait", &trait_).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let strength = |index: usize| {
+            let body: serde_json::Value = serde_json::from_slice(&requests[index].body).unwrap();
+            body["strength"].clone()
+        };
+        assert_eq!(strength(0), 0.12);
+        assert_eq!(strength(1), 0.1235);
+        assert_eq!(
+            requests[0].headers.get("content-type").unwrap(),
+            "application/json"
+        );
+        // The client's setting does not leak into other serialization.
+        assert_eq!(serde_json::to_value(&trait_).unwrap()["strength"], 0.1235);
+    }
+
+    #[tokio::test]
+    async fn test_default_query_is_sent_on_every_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/1"))
+            .and(query_param("tenant", "acme"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/personality"))
+            .and(query_param("tenant", "acme"))
+            .and(query_param("fields", "name"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .default_query("tenant", "acme")
+            .build();
+
+        let get = soulgraph.get("/personality/1").await.unwrap();
+        let post = soulgraph
+            .post("/personality?fields=name", &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(get.status().is_success());
+        assert!(post.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_accepts_str_and_string() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let id = 1;
+
+        assert!(soulgraph.get("/personality/1").await.is_ok());
+        assert!(soulgraph.get(format!("/personality/{id}")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_tracked_from_responses() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-RateLimit-Limit", "100")
+                    .insert_header("X-RateLimit-Remaining", "99")
+                    .insert_header("X-RateLimit-Reset", "1736553600"),
+            )
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        assert_eq!(soulgraph.rate_limit(), None);
+
+        soulgraph.get("/personality/1").await.unwrap();
+
+        assert_eq!(
+            soulgraph.rate_limit(),
+            Some(RateLimit {
+                limit: 100,
+                remaining: 99,
+                reset_at: 1736553600,
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_timeout_is_applied() {
+        let soulgraph = Soulgraph::new("test-key", "http://test.com");
+        assert_eq!(soulgraph.timeout(), Some(DEFAULT_TIMEOUT));
+    }
+
+    #[test]
+    fn test_timeout_can_be_overridden() {
+        let longer = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url("http://test.com")
+            .timeout(Duration::from_secs(300))
+            .build();
+        let unbounded = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url("http://test.com")
+            .no_timeout()
+            .build();
+
+        assert_eq!(longer.timeout(), Some(Duration::from_secs(300)));
+        assert_eq!(unbounded.timeout(), None);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_bounds_slow_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .timeout(Duration::from_millis(50))
+            .build();
+
+        let err = soulgraph.get("/personality/1").await.unwrap_err();
+        assert!(matches!(err, SoulgraphError::Http(ref e) if e.is_timeout()));
+    }
+
+    #[tokio::test]
+    async fn test_pool_settings_are_applied() {
+        let builder = SoulgraphBuilder::new();
+        assert_eq!(builder.pool_max_idle_per_host, None);
+        assert_eq!(builder.pool_idle_timeout, None);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let builder = builder
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .pool_max_idle_per_host(4)
+            .pool_idle_timeout(Duration::from_secs(5));
+        assert_eq!(builder.pool_max_idle_per_host, Some(4));
+        assert_eq!(builder.pool_idle_timeout, Some(Duration::from_secs(5)));
+
+        let soulgraph = builder.build();
+        assert!(soulgraph.get("/personality/1").await.is_ok());
+        assert!(soulgraph.get("/personality/2").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_overrides_client_default() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(300)))
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .timeout(Duration::from_millis(50))
+            .build();
+
+        let response = soulgraph
+            .get_with(
+                "/personality/1",
+                RequestOptions::default().timeout(Duration::from_secs(5)),
+            )
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let err = soulgraph
+            .get_with(
+                "/personality/1",
+                RequestOptions::default().timeout(Duration::from_millis(20)),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SoulgraphError::Http(ref e) if e.is_timeout()));
+    }
+
+    #[derive(Default)]
+    struct RecordingMiddleware {
+        statuses: Mutex<Vec<u16>>,
+    }
+
+    impl RequestMiddleware for RecordingMiddleware {
+        fn before<'a>(&'a self, request: &'a mut reqwest::Request) -> BoxFuture<'a, ()> {
+            request
+                .headers_mut()
+                .insert("x-signature", "signed".parse().unwrap());
+            Box::pin(async {})
+        }
+
+        fn after<'a>(&'a self, response: &'a Response) -> BoxFuture<'a, ()> {
+            self.statuses
+                .lock()
+                .unwrap()
+                .push(response.status().as_u16());
+            Box::pin(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_around_requests() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/1"))
+            .and(header("x-signature", "signed"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(header("x-signature", "signed"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(RecordingMiddleware::default());
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .middleware(recorder.clone())
+            .build();
+
+        soulgraph.get("/personality/1").await.unwrap();
+        soulgraph.delete("/personality/2").await.unwrap();
+
+        assert_eq!(*recorder.statuses.lock().unwrap(), vec![200, 404]);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_coalesces_concurrent_gets() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/soul/abc"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "name": "Dr. Luna" }))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .single_flight(true)
+            .build();
+
+        let responses =
+            futures::future::join_all((0..10).map(|_| soulgraph.get("/soul/abc"))).await;
+        for response in responses {
+            let response = response.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.url().as_str(),
+                format!("{}/soul/abc", server.uri())
+            );
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["name"], "Dr. Luna");
+        }
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // Once the flight has landed, the next GET goes upstream again.
+        server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let response = soulgraph.get("/soul/abc").await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Streamed responses are never buffered, so they are never shared.
+        server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([]))
+                    .set_delay(Duration::from_millis(100)),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        let streamed = || async {
+            futures::StreamExt::collect::<Vec<_>>(memories::MemoryCollection::stream(&soulgraph))
+                .await
+        };
+        let (first, second) = futures::join!(streamed(), streamed());
+        assert!(first.is_empty() && second.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_builder_danger_accept_invalid_certs() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let builder = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .danger_accept_invalid_certs(true);
+        assert!(format!("{builder:?}").contains("danger_accept_invalid_certs: true"));
+
+        let soulgraph = builder.build();
+        let response = soulgraph.get("/personality/1").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_request_escape_hatch() {
+        let server = MockServer::start().await;
+        Mock::given(method("OPTIONS"))
+            .and(path("/personality/1"))
+            .and(query_param("tenant", "acme"))
+            .and(header("X-API-KEY", "test-key"))
+            .and(header("X-Trace", "abc"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .default_query("tenant", "acme")
+            .build();
+
+        let request = soulgraph
+            .request(Method::OPTIONS, "/personality/1")
+            .header("X-Trace", "abc")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            format!("{}/personality/1?tenant=acme", server.uri())
+        );
+        assert_eq!(request.headers()["X-API-KEY"], "test-key");
+
+        let response = soulgraph
+            .request(Method::OPTIONS, "/personality/1")
+            .header("X-Trace", "abc")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl_serves_repeats_until_expiry() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Soul::default()))
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .cache_ttl(Duration::from_millis(200))
+            .build();
+        let hits = || async { server.received_requests().await.unwrap().len() };
+
+        let first = Soul::get("abc", &soulgraph).await.unwrap();
+        let second = Soul::get("abc", &soulgraph).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(hits().await, 1);
+
+        // Same URL, different type: cached separately.
+        personality::Personality::get("abc", &soulgraph)
+            .await
+            .unwrap_err();
+        assert_eq!(hits().await, 2);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        Soul::get("abc", &soulgraph).await.unwrap();
+        assert_eq!(hits().await, 3);
+
+        soulgraph.clear_cache();
+        Soul::get("abc", &soulgraph).await.unwrap();
+        assert_eq!(hits().await, 4);
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_evicted_by_own_writes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/abc"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(personality::Personality::default()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/personality/abc"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(personality::Personality::default()),
+            )
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .cache_ttl(Duration::from_secs(60))
+            .build();
+        let gets = || async {
+            server
+                .received_requests()
+                .await
+                .unwrap()
+                .iter()
+                .filter(|request| request.method == Method::GET)
+                .count()
+        };
+
+        let personality = personality::Personality::get("abc", &soulgraph)
+            .await
+            .unwrap();
+        personality::Personality::get("abc", &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(gets().await, 1);
+
+        personality::Personality::update("abc", &personality, &soulgraph)
+            .await
+            .unwrap();
+        personality::Personality::get("abc", &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(gets().await, 2);
+
+        personality::Personality::replace("abc", &personality, &soulgraph)
+            .await
+            .unwrap();
+        personality::Personality::get("abc", &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(gets().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_reads_that_race_a_write() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/abc"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(personality::Personality::default())
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/personality/abc"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(personality::Personality::default()),
+            )
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .cache_ttl(Duration::from_secs(60))
+            .build();
+        let gets = || async {
+            server
+                .received_requests()
+                .await
+                .unwrap()
+                .iter()
+                .filter(|request| request.method == Method::GET)
+                .count()
+        };
+
+        // The GET starts before the PATCH and completes after it.
+        let (read, written) =
+            futures::join!(personality::Personality::get("abc", &soulgraph), async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                personality::Personality::update(
+                    "abc",
+                    &personality::Personality::default(),
+                    &soulgraph,
+                )
+                .await
+            });
+        read.unwrap();
+        written.unwrap();
+        assert_eq!(gets().await, 1);
+
+        personality::Personality::get("abc", &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(gets().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_builder_extra_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("X-Org-ID", "org-42"))
+            .and(header("X-API-KEY", "test-key"))
+            .and(header("Content-Type", "application/json"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .header("X-Org-ID", "org-42")
+            .header("X-API-KEY", "ignored")
+            .build();
+
+        let response = soulgraph.get("/personality/1").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_are_redacted() {
+        let builder = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url("http://test.com")
+            .header("X-Gateway-Token", "gateway-secret")
+            .dry_run(true);
+        let builder_debug = format!("{builder:?}");
+        assert!(builder_debug.contains("X-Gateway-Token"));
+        assert!(!builder_debug.contains("gateway-secret"));
+
+        let soulgraph = builder.build();
+        let client_debug = format!("{soulgraph:?}");
+        assert!(client_debug.contains("x-gateway-token"));
+        assert!(!client_debug.contains("gateway-secret"));
+
+        let err = soulgraph.get("/personality/1").await.unwrap_err();
+        let SoulgraphError::DryRun(request) = err else {
+            panic!("expected a dry-run request, got {err:?}");
+        };
+        assert!(request
+            .headers
+            .contains(&("x-gateway-token".to_string(), "***".to_string())));
+        assert!(request
+            .headers
+            .contains(&("content-type".to_string(), "application/json".to_string())));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid header name")]
+    fn test_builder_invalid_header_name() {
+        let _ = SoulgraphBuilder::new()
+            .api_key("test-key")
+            .base_url("http://test.com")
+            .header("bad header", "value")
+            .build();
+    }
+
     #[test]
     #[should_panic(expected = "Missing api key")]
     fn test_builder_missing_key() {
-        SoulgraphBuilder::new().base_url("http://test.com").build();
+        let _ = SoulgraphBuilder::new().base_url("http://test.com").build();
     }
 
     #[test]
     #[should_panic(expected = "Missing base url")]
     fn test_builder_missing_url() {
-        SoulgraphBuilder::new().api_key("test-key").build();
+        let _ = SoulgraphBuilder::new().api_key("test-key").build();
     }
 }
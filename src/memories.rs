@@ -1,132 +1,535 @@
-mod fragment;
+pub mod fragment;
+mod json_array;
+mod patch;
 
 use fragment::Fragment;
+use futures::stream::{self, Stream, StreamExt};
+pub use patch::PatchError;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error, fmt};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::Path,
+};
 use uuid::Uuid;
 
-use crate::Soulgraph;
+use crate::{
+    response::{self, DeleteResult},
+    RequestOptions, Soulgraph, SoulgraphError,
+};
 
-#[derive(Debug)]
-pub struct CorruptMemory;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryCollection {
+    pub memories: HashMap<Uuid, Memory>,
+}
+
+impl MemoryCollection {
+    /// Importance-weighted mean valence and intensity across all memories, or `None` when
+    /// the collection is empty.
+    ///
+    /// Memories are weighted equally when every importance score is zero. Results are
+    /// clamped to the valid valence and intensity ranges.
+    pub fn average_emotion(&self) -> Option<EmotionalSignature> {
+        if self.memories.is_empty() {
+            return None;
+        }
+        let total_importance: f32 = self
+            .memories
+            .values()
+            .map(|memory| memory.importance_score)
+            .sum();
+        let weight = |memory: &Memory| {
+            if total_importance > 0.0 {
+                memory.importance_score / total_importance
+            } else {
+                1.0 / self.memories.len() as f32
+            }
+        };
 
-impl fmt::Display for CorruptMemory {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "personality is corrupted")
+        let (valence, intensity) =
+            self.memories
+                .values()
+                .fold((0.0, 0.0), |(valence, intensity), memory| {
+                    let signature = &memory.emotional_signature;
+                    (
+                        valence + weight(memory) * signature.valence,
+                        intensity + weight(memory) * signature.intensity,
+                    )
+                });
+        Some(EmotionalSignature {
+            id: None,
+            valence: valence.clamp(-1.0, 1.0),
+            intensity: intensity.clamp(0.0, 1.0),
+        })
     }
-}
 
-impl error::Error for CorruptMemory {}
+    /// Write the collection to `path` as pretty-printed JSON, keyed by memory id.
+    pub fn to_file(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a collection previously written with [`MemoryCollection::to_file`].
+    pub fn from_file(path: &Path) -> Result<MemoryCollection, SoulgraphError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
 
+    /// Streams every memory from `/memory`, decoding the JSON array response one element
+    /// at a time instead of buffering the whole collection.
+    ///
+    /// The response is always read as a bare array, even when
+    /// [`SoulgraphBuilder::envelope`](crate::SoulgraphBuilder::envelope) is enabled.
+    pub fn stream(soul: &Soulgraph) -> impl Stream<Item = Result<Memory, SoulgraphError>> + '_ {
+        stream::once(async move {
+            let response = soul
+                .get_with("/memory", RequestOptions::default().streaming())
+                .await?;
+            response::check_status(response).await
+        })
+        .flat_map(|response| match response {
+            Ok(response) => json_array::elements(response.bytes_stream()).left_stream(),
+            Err(err) => stream::iter([Err(err)]).right_stream(),
+        })
+    }
+}
+
+/// A soul's memories as reorganized by server-side consolidation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MemoryCollection {
+pub struct MemoryGraph {
+    #[serde(default)]
     pub memories: HashMap<Uuid, Memory>,
+    pub indices: MemoryIndices,
+    pub stats: MemoryStats,
+}
+
+/// Lookup tables from a topic tag or memory type to the memories carrying it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryIndices {
+    #[serde(default)]
+    pub by_topic: HashMap<String, Vec<Uuid>>,
+    #[serde(default)]
+    pub by_type: HashMap<String, Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryStats {
+    pub total_memories: usize,
+    pub total_fragments: usize,
+    pub average_importance: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_consolidated: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct TimeRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<i64>,
+}
+
+/// One piece of a memory's content uploaded by `Memory::create_chunked`.
+#[derive(Serialize)]
+struct MemoryChunk<'a> {
+    index: usize,
+    total: usize,
+    content: &'a str,
+}
+
+/// Finalizes a chunked upload once every chunk has been sent.
+#[derive(Serialize)]
+struct ChunkCommit {
+    total_chunks: usize,
+    total_bytes: usize,
+}
+
+/// Splits `content` into pieces of at most `chunk_bytes` bytes without breaking a UTF-8 character.
+fn split_chunks(content: &str, chunk_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let mut end = chunk_bytes.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            // A single character wider than `chunk_bytes` is sent whole.
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+/// Collects memories keyed by their `id`.
+///
+/// Memories without an `id` are assigned a freshly generated v4 UUID, which is
+/// written back onto the memory so the key and `Memory::id` always agree.
+impl FromIterator<Memory> for MemoryCollection {
+    fn from_iter<I: IntoIterator<Item = Memory>>(iter: I) -> Self {
+        let memories = iter
+            .into_iter()
+            .map(|mut memory| {
+                let id = *memory.id.get_or_insert_with(Uuid::new_v4);
+                (id, memory)
+            })
+            .collect();
+        Self { memories }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Memory {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub memory: String,
     pub fragments: Vec<Fragment>,
-    #[serde(with = "uuid_vec_format")]
-    pub connections: Vec<Uuid>,
+    pub connections: Vec<Connection>,
     pub emotional_signature: EmotionalSignature,
-    #[serde(with = "validate_importance_score")]
+    #[serde(alias = "importance", with = "validate_importance_score")]
     pub importance_score: f32,
     pub creation_date: i64,
     pub last_accessed: i64,
     pub metadata: MemoryMetadata,
 }
 
+/// A weighted link from one memory to another.
+///
+/// Also deserializes from a bare UUID string, the legacy form, with a weight of `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ConnectionRepr")]
+pub struct Connection {
+    pub target: Uuid,
+    #[serde(serialize_with = "crate::precision::serialize")]
+    pub weight: f32,
+}
+
+impl Connection {
+    pub fn new(target: Uuid, weight: f32) -> Self {
+        Self { target, weight }
+    }
+}
+
+impl From<Uuid> for Connection {
+    fn from(target: Uuid) -> Self {
+        Connection::new(target, DEFAULT_CONNECTION_WEIGHT)
+    }
+}
+
+const DEFAULT_CONNECTION_WEIGHT: f32 = 1.0;
+
+fn default_connection_weight() -> f32 {
+    DEFAULT_CONNECTION_WEIGHT
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConnectionRepr {
+    Legacy(Uuid),
+    Weighted {
+        target: Uuid,
+        #[serde(default = "default_connection_weight")]
+        weight: f32,
+    },
+}
+
+impl From<ConnectionRepr> for Connection {
+    fn from(repr: ConnectionRepr) -> Self {
+        match repr {
+            ConnectionRepr::Legacy(target) => Connection::from(target),
+            ConnectionRepr::Weighted { target, weight } => Connection::new(target, weight),
+        }
+    }
+}
+
 impl Memory {
     /// Creates a new MemoryBuilder instance for constructing a Memory
     pub fn builder() -> MemoryBuilder {
         MemoryBuilder::default()
     }
 
-    /// Get the `Memory` with the given `id`.
-    pub async fn get(id: &str, soul: Soulgraph) -> Result<Memory, CorruptMemory> {
-        if let Ok(response) = soul.get(format!("/personality/{id}").as_str()).await {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Memory>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptMemory),
-                },
-                _ => Err(CorruptMemory),
+    /// Folds `other` into this memory, e.g. when deduplicating.
+    ///
+    /// Fragments are appended, connections and topic tags are unioned (a connection to the
+    /// same target keeps the higher weight), the importance score becomes the maximum of the
+    /// two and the emotional signature their mean. `id` and all other fields are kept.
+    pub fn merge(&mut self, other: &Memory) {
+        self.fragments.extend(other.fragments.iter().cloned());
+
+        for connection in &other.connections {
+            match self
+                .connections
+                .iter_mut()
+                .find(|existing| existing.target == connection.target)
+            {
+                Some(existing) => existing.weight = existing.weight.max(connection.weight),
+                None => self.connections.push(*connection),
             }
+        }
+
+        self.importance_score = self.importance_score.max(other.importance_score);
+
+        let signature = &mut self.emotional_signature;
+        signature.valence = (signature.valence + other.emotional_signature.valence) / 2.0;
+        signature.intensity = (signature.intensity + other.emotional_signature.intensity) / 2.0;
+
+        for tag in &other.metadata.topic_tags {
+            if !self.metadata.topic_tags.contains(tag) {
+                self.metadata.topic_tags.push(tag.clone());
+            }
+        }
+    }
+
+    /// Applies a JSON Patch (RFC 6902) to the memory's JSON form.
+    ///
+    /// Only `add`, `replace` and `remove` are supported. The result is re-validated like a
+    /// server response, and the memory is left unchanged if any step fails. Scores the patch
+    /// does not touch keep their exact values.
+    pub fn apply_json_patch(&mut self, patch: &serde_json::Value) -> Result<(), PatchError> {
+        let mut document = crate::precision::exact(|| serde_json::to_value(&*self))
+            .map_err(PatchError::Invalid)?;
+        patch::apply(&mut document, patch)?;
+        *self = serde_json::from_value(document).map_err(PatchError::Invalid)?;
+        Ok(())
+    }
+
+    /// The `n` connections with the highest weight, strongest first; ties keep their order.
+    pub fn strongest_connections(&self, n: usize) -> Vec<&Connection> {
+        let mut connections: Vec<&Connection> = self.connections.iter().collect();
+        connections.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        connections.truncate(n);
+        connections
+    }
+
+    /// Distinct `Context` topics across all fragments of this memory.
+    pub fn topics(&self) -> HashSet<String> {
+        self.fragments
+            .iter()
+            .map(|fragment| fragment.context.topic.clone())
+            .collect()
+    }
+
+    /// Fragments whose `Context` topic equals `topic`.
+    pub fn fragments_for_topic(&self, topic: &str) -> Vec<&Fragment> {
+        self.fragments
+            .iter()
+            .filter(|fragment| fragment.context.topic == topic)
+            .collect()
+    }
+
+    /// Recomputes the emotional signature from the fragments: valence becomes the
+    /// importance-weighted mean of their `emotional_valence` and intensity the mean of their
+    /// `importance`, both clamped. Does nothing when there are no fragments.
+    ///
+    /// Fragments are weighted equally when every importance is zero.
+    pub fn derive_emotional_signature(&mut self) {
+        if self.fragments.is_empty() {
+            return;
+        }
+        let count = self.fragments.len() as f32;
+        let total_importance: f32 = self.fragments.iter().map(|f| f.importance).sum();
+        let valence: f32 = if total_importance > 0.0 {
+            self.fragments
+                .iter()
+                .map(|f| f.importance * f.emotional_valence)
+                .sum::<f32>()
+                / total_importance
         } else {
-            Err(CorruptMemory)
+            self.fragments
+                .iter()
+                .map(|f| f.emotional_valence)
+                .sum::<f32>()
+                / count
+        };
+
+        self.emotional_signature.valence = valence.clamp(-1.0, 1.0);
+        self.emotional_signature.intensity = (total_importance / count).clamp(0.0, 1.0);
+    }
+
+    /// Fragments sorted by ascending `timestamp`, keeping insertion order for ties.
+    pub fn fragments_chronological(&self) -> Vec<&Fragment> {
+        let mut fragments: Vec<&Fragment> = self.fragments.iter().collect();
+        fragments.sort_by(|a, b| a.cmp_by_time(b));
+        fragments
+    }
+
+    /// Whether a `Memory` is stored under `id`, checked without fetching it.
+    pub async fn exists(id: &str, soul: &Soulgraph) -> Result<bool, SoulgraphError> {
+        let response = soul.head(format!("/memory/{id}")).await?;
+        response::exists(response).await
+    }
+
+    /// Get the `Memory` with the given `id`.
+    pub async fn get(id: &str, soul: &Soulgraph) -> Result<Memory, SoulgraphError> {
+        soul.get_cached(&format!("/personality/{id}")).await
+    }
+
+    /// Records an access of the `Memory` stored under `id` by PATCHing its `last_accessed`
+    /// to the current time, which is returned in Unix milliseconds.
+    pub async fn touch(id: &str, soul: &Soulgraph) -> Result<i64, SoulgraphError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let response = soul
+            .patch(
+                format!("/memory/{id}"),
+                &serde_json::json!({ "last_accessed": now }),
+            )
+            .await?;
+        response::check_status(response).await?;
+        Ok(now)
+    }
+
+    /// [`Memory::get`] followed by [`Memory::touch`], so client-driven recency decay sees
+    /// the read. The touch is best-effort: if it fails the fetched memory is still
+    /// returned, with its `last_accessed` as the server sent it.
+    pub async fn get_and_touch(id: &str, soul: &Soulgraph) -> Result<Memory, SoulgraphError> {
+        let mut memory = Memory::get(id, soul).await?;
+        match Memory::touch(id, soul).await {
+            Ok(accessed) => memory.last_accessed = accessed,
+            #[cfg(feature = "tracing")]
+            Err(err) => tracing::warn!(%id, %err, "failed to touch memory"),
+            #[cfg(not(feature = "tracing"))]
+            Err(_) => {}
         }
+        Ok(memory)
     }
 
     /// Create a `Memory`.
-    pub async fn create(personality: &Memory, soul: Soulgraph) -> Result<Memory, CorruptMemory> {
-        if let Ok(response) = soul.post("/personality", personality).await {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Memory>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptMemory),
-                },
-                _ => Err(CorruptMemory),
-            }
-        } else {
-            Err(CorruptMemory)
+    pub async fn create(personality: &Memory, soul: &Soulgraph) -> Result<Memory, SoulgraphError> {
+        let response = soul.post("/personality", personality).await?;
+        response::decode(response).await
+    }
+
+    /// Create a `Memory` whose `memory` text is uploaded in sequential chunks of at most
+    /// `chunk_bytes` bytes, for content too large for a single request.
+    ///
+    /// The memory is first created with empty text, each chunk is then POSTed to
+    /// `/memory/{id}/chunk`, and `/memory/{id}/commit` returns the assembled memory.
+    pub async fn create_chunked(
+        memory: &Memory,
+        chunk_bytes: usize,
+        soul: &Soulgraph,
+    ) -> Result<Memory, SoulgraphError> {
+        if chunk_bytes == 0 {
+            return Err(SoulgraphError::InvalidInput(
+                "chunk_bytes must be greater than zero".to_string(),
+            ));
+        }
+
+        let shell = Memory {
+            memory: String::new(),
+            ..memory.clone()
+        };
+        let id = Memory::create(&shell, soul).await?.id.ok_or_else(|| {
+            SoulgraphError::InvalidInput("server did not return an id for the memory".to_string())
+        })?;
+
+        let chunks = split_chunks(&memory.memory, chunk_bytes);
+        for (index, content) in chunks.iter().enumerate() {
+            let chunk = MemoryChunk {
+                index,
+                total: chunks.len(),
+                content,
+            };
+            let response = soul.post(format!("/memory/{id}/chunk"), &chunk).await?;
+            response::check_status(response).await?;
         }
+
+        let commit = ChunkCommit {
+            total_chunks: chunks.len(),
+            total_bytes: memory.memory.len(),
+        };
+        let response = soul.post(format!("/memory/{id}/commit"), &commit).await?;
+        response::decode(response).await
     }
 
-    /// Delete a `Memory` stored under `id`.
-    pub async fn delete(id: &str, soul: Soulgraph) -> Result<(), CorruptMemory> {
-        if let Ok(response) = soul.delete(format!("/personality/{id}").as_str()).await {
-            match response.status() {
-                reqwest::StatusCode::OK => Ok(()),
-                _ => Err(CorruptMemory),
+    /// Memories tagged with `tag`, fetched with `GET /memory?tag={tag}`.
+    pub async fn find_by_tag(tag: &str, soul: &Soulgraph) -> Result<Vec<Memory>, SoulgraphError> {
+        let tag: String = url::form_urlencoded::byte_serialize(tag.as_bytes()).collect();
+        let response = soul.get(format!("/memory?tag={tag}")).await?;
+        response::decode(response).await
+    }
+
+    /// Search for memories created within `after_ms..=before_ms` (Unix millis), either bound optional.
+    pub async fn search_by_time(
+        after_ms: Option<i64>,
+        before_ms: Option<i64>,
+        soul: &Soulgraph,
+    ) -> Result<Vec<Memory>, SoulgraphError> {
+        if let (Some(after), Some(before)) = (after_ms, before_ms) {
+            if after > before {
+                return Err(SoulgraphError::InvalidInput(format!(
+                    "after ({after}) must not be later than before ({before})"
+                )));
             }
-        } else {
-            Err(CorruptMemory)
         }
+
+        let range = TimeRange {
+            after: after_ms,
+            before: before_ms,
+        };
+        let response = soul.post("/memory/search", &range).await?;
+        response::decode(response).await
     }
-}
 
-mod uuid_vec_format {
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
-    use uuid::Uuid;
+    /// Trigger server-side consolidation of the memories of the soul stored under `soul_id`.
+    pub async fn consolidate(
+        soul_id: &str,
+        soul: &Soulgraph,
+    ) -> Result<MemoryGraph, SoulgraphError> {
+        Memory::consolidate_with(soul_id, soul, RequestOptions::default()).await
+    }
 
-    pub fn serialize<S>(uuids: &[Uuid], serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let strings: Vec<String> = uuids.iter().map(|u| u.to_string()).collect();
-        strings.serialize(serializer)
+    /// [`Memory::consolidate`] with per-request `options`, e.g. a longer timeout for large souls.
+    pub async fn consolidate_with(
+        soul_id: &str,
+        soul: &Soulgraph,
+        options: RequestOptions,
+    ) -> Result<MemoryGraph, SoulgraphError> {
+        let response = soul
+            .post_with(
+                format!("/soul/{soul_id}/memory/consolidate"),
+                &serde_json::json!({}),
+                options,
+            )
+            .await?;
+        response::decode(response).await
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let strings: Vec<String> = Vec::deserialize(deserializer)?;
-        strings
-            .into_iter()
-            .map(|s| Uuid::parse_str(&s).map_err(serde::de::Error::custom))
-            .collect()
+    /// Delete a `Memory` stored under `id`.
+    pub async fn delete(id: &str, soul: &Soulgraph) -> Result<DeleteResult, SoulgraphError> {
+        let response = soul.delete(format!("/personality/{id}")).await?;
+        response::delete_result(response, id).await
+    }
+}
+
+impl TryFrom<serde_json::Value> for Memory {
+    type Error = SoulgraphError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(serde_json::from_value(value)?)
     }
 }
 
 mod validate_importance_score {
-    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::{Deserializer, Serializer};
 
     pub fn serialize<S>(score: &f32, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_f32(*score)
+        crate::precision::serialize(score, serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<f32, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let score = f32::deserialize(deserializer)?;
+        let score = crate::number::deserialize(deserializer)?;
         if !(0.0..=1.0).contains(&score) {
             return Err(serde::de::Error::custom(
                 "importance_score must be between 0 and 1",
@@ -155,7 +558,11 @@ impl Default for Memory {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmotionalSignature {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     #[serde(with = "validate_valence")]
     pub valence: f32,
@@ -163,6 +570,44 @@ pub struct EmotionalSignature {
     pub intensity: f32,
 }
 
+/// Valence within `±NEUTRAL_VALENCE` of zero reads as [`Mood::Neutral`].
+const NEUTRAL_VALENCE: f32 = 0.2;
+/// Intensity at or above which a mood counts as high-energy.
+const HIGH_INTENSITY: f32 = 0.5;
+/// Valence at or below which a high-energy negative mood is [`Mood::Angry`].
+const ANGRY_VALENCE: f32 = -0.6;
+
+/// A named mood derived from an [`EmotionalSignature`]; see [`EmotionalSignature::mood`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mood {
+    Joyful,
+    Content,
+    Neutral,
+    Sad,
+    Anxious,
+    Angry,
+}
+
+impl EmotionalSignature {
+    /// Classifies the signature by valence/intensity quadrant.
+    ///
+    /// Valence strictly between -0.2 and 0.2 is `Neutral`. Otherwise intensity of 0.5 or
+    /// more is high-energy: positive is `Joyful`, negative is `Angry` at valence -0.6 or
+    /// below and `Anxious` above it. Lower intensity is `Content` when positive and `Sad`
+    /// when negative.
+    pub fn mood(&self) -> Mood {
+        let high = self.intensity >= HIGH_INTENSITY;
+        match self.valence {
+            valence if valence.abs() < NEUTRAL_VALENCE => Mood::Neutral,
+            valence if valence > 0.0 && high => Mood::Joyful,
+            valence if valence > 0.0 => Mood::Content,
+            valence if high && valence <= ANGRY_VALENCE => Mood::Angry,
+            _ if high => Mood::Anxious,
+            _ => Mood::Sad,
+        }
+    }
+}
+
 impl Default for EmotionalSignature {
     fn default() -> Self {
         Self {
@@ -180,7 +625,7 @@ mod validate_valence {
     where
         S: Serializer,
     {
-        serializer.serialize_f32(*valence)
+        crate::precision::serialize(valence, serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<f32, D::Error>
@@ -202,7 +647,7 @@ mod validate_intensity {
     where
         S: Serializer,
     {
-        serializer.serialize_f32(*intensity)
+        crate::precision::serialize(intensity, serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<f32, D::Error>
@@ -221,7 +666,11 @@ mod validate_intensity {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryMetadata {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub topic_tags: Vec<String>,
     pub personality_influence: Vec<String>,
@@ -239,11 +688,84 @@ impl Default for MemoryMetadata {
     }
 }
 
+/// Builds a [`MemoryMetadata`]; repeated topic tags are kept once.
+#[derive(Default)]
+pub struct MemoryMetadataBuilder {
+    topic_tags: Vec<String>,
+    personality_influence: Vec<String>,
+    memory_type: Option<String>,
+}
+
+impl MemoryMetadataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn add_topic_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.topic_tags.contains(&tag) {
+            self.topic_tags.push(tag);
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn add_personality_influence(mut self, influence: impl Into<String>) -> Self {
+        self.personality_influence.push(influence.into());
+        self
+    }
+
+    #[must_use]
+    pub fn memory_type(mut self, memory_type: impl Into<String>) -> Self {
+        self.memory_type = Some(memory_type.into());
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> MemoryMetadata {
+        let defaults = MemoryMetadata::default();
+        MemoryMetadata {
+            id: None,
+            topic_tags: self.topic_tags,
+            personality_influence: self.personality_influence,
+            memory_type: self.memory_type.unwrap_or(defaults.memory_type),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemoryBuilderError {
+    EmptyMemory,
+    InvalidImportance(f32),
+    InvalidValence(f32),
+    InvalidIntensity(f32),
+}
+
+impl std::fmt::Display for MemoryBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryBuilderError::EmptyMemory => write!(f, "memory content must not be empty"),
+            MemoryBuilderError::InvalidImportance(score) => {
+                write!(f, "importance_score must be between 0 and 1, got {score}")
+            }
+            MemoryBuilderError::InvalidValence(valence) => {
+                write!(f, "valence must be between -1 and 1, got {valence}")
+            }
+            MemoryBuilderError::InvalidIntensity(intensity) => {
+                write!(f, "intensity must be between 0 and 1, got {intensity}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MemoryBuilderError {}
+
 #[derive(Default)]
 pub struct MemoryBuilder {
     memory: String,
     fragments: Vec<Fragment>,
-    connections: Vec<Uuid>,
+    connections: Vec<Connection>,
     emotional_signature: Option<EmotionalSignature>,
     importance_score: f32,
     creation_date: i64,
@@ -260,36 +782,78 @@ impl MemoryBuilder {
         }
     }
 
+    #[must_use]
     pub fn add_fragment(mut self, fragment: Fragment) -> Self {
         self.fragments.push(fragment);
         self
     }
 
+    #[must_use]
     pub fn add_connection(mut self, connection: Uuid) -> Self {
-        self.connections.push(connection);
+        self.connections.push(Connection::from(connection));
         self
     }
 
+    #[must_use]
+    pub fn add_weighted_connection(mut self, connection: Uuid, weight: f32) -> Self {
+        self.connections.push(Connection::new(connection, weight));
+        self
+    }
+
+    #[must_use]
     pub fn emotional_signature(mut self, signature: EmotionalSignature) -> Self {
         self.emotional_signature = Some(signature);
         self
     }
 
+    #[must_use]
     pub fn importance_score(mut self, score: f32) -> Self {
         self.importance_score = score;
         self
     }
 
+    #[must_use]
     pub fn last_accessed(mut self, timestamp: i64) -> Self {
         self.last_accessed = Some(timestamp);
         self
     }
 
+    #[must_use]
     pub fn metadata(mut self, metadata: MemoryMetadata) -> Self {
         self.metadata = Some(metadata);
         self
     }
 
+    /// Builds the metadata inline, e.g. `.metadata_builder(|mb| mb.add_topic_tag("trading"))`.
+    #[must_use]
+    pub fn metadata_builder(
+        self,
+        configure: impl FnOnce(MemoryMetadataBuilder) -> MemoryMetadataBuilder,
+    ) -> Self {
+        self.metadata(configure(MemoryMetadataBuilder::new()).build())
+    }
+
+    /// Like [`MemoryBuilder::build`], but rejects blank memory content and the
+    /// out-of-range scores the server would refuse to deserialize.
+    pub fn try_build(self) -> Result<Memory, MemoryBuilderError> {
+        if self.memory.trim().is_empty() {
+            return Err(MemoryBuilderError::EmptyMemory);
+        }
+        if !(0.0..=1.0).contains(&self.importance_score) {
+            return Err(MemoryBuilderError::InvalidImportance(self.importance_score));
+        }
+        if let Some(signature) = &self.emotional_signature {
+            if !(-1.0..=1.0).contains(&signature.valence) {
+                return Err(MemoryBuilderError::InvalidValence(signature.valence));
+            }
+            if !(0.0..=1.0).contains(&signature.intensity) {
+                return Err(MemoryBuilderError::InvalidIntensity(signature.intensity));
+            }
+        }
+        Ok(self.build())
+    }
+
+    #[must_use]
     pub fn build(self) -> Memory {
         Memory {
             id: None,
@@ -310,6 +874,11 @@ impl MemoryBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{body_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
 
     #[test]
     fn test_default_core_memory() {
@@ -324,6 +893,64 @@ mod tests {
         assert!(memory.metadata.topic_tags.is_empty());
     }
 
+    #[test]
+    fn test_metadata_builder() {
+        let memory = MemoryBuilder::new("test memory".to_string())
+            .metadata_builder(|metadata| {
+                metadata
+                    .add_topic_tag("trading")
+                    .add_topic_tag("risk")
+                    .add_topic_tag("trading")
+                    .add_personality_influence("cautious")
+                    .memory_type("episodic")
+            })
+            .build();
+
+        assert_eq!(memory.metadata.topic_tags, vec!["trading", "risk"]);
+        assert_eq!(memory.metadata.personality_influence, vec!["cautious"]);
+        assert_eq!(memory.metadata.memory_type, "episodic");
+        assert_eq!(MemoryMetadataBuilder::new().build().memory_type, "default");
+    }
+
+    #[test]
+    fn test_try_build() {
+        let memory = MemoryBuilder::new("first trade".to_string())
+            .importance_score(0.6)
+            .emotional_signature(EmotionalSignature {
+                id: None,
+                valence: -0.4,
+                intensity: 0.9,
+            })
+            .try_build()
+            .unwrap();
+        assert_eq!(memory.memory, "first trade");
+        assert_eq!(memory.importance_score, 0.6);
+
+        let err = MemoryBuilder::new("  ".to_string())
+            .try_build()
+            .unwrap_err();
+        assert_eq!(err, MemoryBuilderError::EmptyMemory);
+        assert_eq!(err.to_string(), "memory content must not be empty");
+        assert_eq!(
+            MemoryBuilder::new("m".to_string())
+                .importance_score(1.5)
+                .try_build()
+                .unwrap_err(),
+            MemoryBuilderError::InvalidImportance(1.5)
+        );
+        assert_eq!(
+            MemoryBuilder::new("m".to_string())
+                .emotional_signature(EmotionalSignature {
+                    id: None,
+                    valence: 0.0,
+                    intensity: -0.1,
+                })
+                .try_build()
+                .unwrap_err(),
+            MemoryBuilderError::InvalidIntensity(-0.1)
+        );
+    }
+
     #[test]
     fn test_builder_pattern() {
         let uuid = Uuid::new_v4();
@@ -341,12 +968,758 @@ mod tests {
         assert!(memory.id.is_none());
         assert_eq!(memory.memory, "test memory");
         assert_eq!(memory.fragments.len(), 1);
-        assert_eq!(memory.connections, vec![uuid]);
+        assert_eq!(memory.connections, vec![Connection::new(uuid, 1.0)]);
         assert_eq!(memory.emotional_signature.valence, 0.5);
         assert_eq!(memory.emotional_signature.intensity, 0.7);
         assert_eq!(memory.importance_score, 0.8);
     }
 
+    fn fragment_with_topic(topic: &str) -> Fragment {
+        Fragment::builder()
+            .context(fragment::Context {
+                id: None,
+                topic: topic.to_string(),
+                ..fragment::Context::default()
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_topics() {
+        let memory = MemoryBuilder::new("test memory".to_string())
+            .add_fragment(fragment_with_topic("trading"))
+            .add_fragment(fragment_with_topic("risk"))
+            .add_fragment(fragment_with_topic("trading"))
+            .build();
+
+        let topics = memory.topics();
+        assert_eq!(topics.len(), 2);
+        assert!(topics.contains("trading"));
+        assert!(topics.contains("risk"));
+    }
+
+    #[test]
+    fn test_fragments_for_topic() {
+        let memory = MemoryBuilder::new("test memory".to_string())
+            .add_fragment(fragment_with_topic("trading"))
+            .add_fragment(fragment_with_topic("risk"))
+            .add_fragment(fragment_with_topic("trading"))
+            .build();
+
+        assert_eq!(memory.fragments_for_topic("trading").len(), 2);
+        assert_eq!(memory.fragments_for_topic("risk").len(), 1);
+        assert!(memory.fragments_for_topic("weather").is_empty());
+    }
+
+    #[test]
+    fn test_fragments_chronological() {
+        let at = |timestamp: i64, content: &str| {
+            Fragment::builder()
+                .content(content.to_string())
+                .timestamp(timestamp)
+                .build()
+        };
+        let memory = MemoryBuilder::new("timeline".to_string())
+            .add_fragment(at(30, "third"))
+            .add_fragment(at(10, "first"))
+            .add_fragment(at(20, "second-a"))
+            .add_fragment(at(20, "second-b"))
+            .build();
+
+        let contents: Vec<&str> = memory
+            .fragments_chronological()
+            .iter()
+            .map(|fragment| fragment.content.as_str())
+            .collect();
+        assert_eq!(contents, ["first", "second-a", "second-b", "third"]);
+        assert_eq!(
+            memory.fragments[1].cmp_by_time(&memory.fragments[0]),
+            std::cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_memory_try_from_value() {
+        let value = serde_json::to_value(
+            MemoryBuilder::new("test memory".to_string())
+                .importance_score(0.8)
+                .build(),
+        )
+        .unwrap();
+        let memory = Memory::try_from(value.clone()).unwrap();
+        assert_eq!(memory.memory, "test memory");
+
+        let mut invalid = value;
+        invalid["importance_score"] = json!(1.5);
+        let result = Memory::try_from(invalid);
+        assert!(matches!(result, Err(SoulgraphError::Decode(_))));
+    }
+
+    #[test]
+    fn test_memory_legacy_importance_field() {
+        let mut value = serde_json::to_value(
+            MemoryBuilder::new("test memory".to_string())
+                .importance_score(0.8)
+                .build(),
+        )
+        .unwrap();
+        let importance = value
+            .as_object_mut()
+            .unwrap()
+            .remove("importance_score")
+            .unwrap();
+        value["importance"] = importance;
+
+        let memory = Memory::try_from(value).unwrap();
+        assert_eq!(memory.importance_score, 0.8);
+        assert!(serde_json::to_value(&memory)
+            .unwrap()
+            .get("importance_score")
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_memory_delete_accepts_empty_no_content() {
+        let server = MockServer::start().await;
+        let id = "550e8400-e29b-41d4-a716-446655440000";
+        Mock::given(method("DELETE"))
+            .and(path(format!("/personality/{id}")))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let result = Memory::delete(id, &Soulgraph::new("test-key", &server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            DeleteResult {
+                id: Uuid::parse_str(id).unwrap(),
+                deleted: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_collect_memory_collection() {
+        let id = Uuid::new_v4();
+        let mut with_id = MemoryBuilder::new("known".to_string()).build();
+        with_id.id = Some(id);
+        let without_id = MemoryBuilder::new("unknown".to_string()).build();
+
+        let collection: MemoryCollection = vec![with_id, without_id].into_iter().collect();
+
+        assert_eq!(collection.memories.len(), 2);
+        assert_eq!(collection.memories[&id].memory, "known");
+        let (generated, memory) = collection
+            .memories
+            .iter()
+            .find(|(key, _)| **key != id)
+            .unwrap();
+        assert_eq!(memory.memory, "unknown");
+        assert_eq!(memory.id, Some(*generated));
+    }
+
+    #[test]
+    fn test_split_chunks_respects_char_boundaries() {
+        assert_eq!(split_chunks("aaaabbbbcc", 4), vec!["aaaa", "bbbb", "cc"]);
+        assert_eq!(split_chunks("héllo", 2), vec!["h", "é", "ll", "o"]);
+        assert_eq!(split_chunks("é", 1), vec!["é"]);
+        assert!(split_chunks("", 4).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_chunked_uploads_three_chunks() {
+        let server = MockServer::start().await;
+        let id = Uuid::new_v4();
+        let memory = MemoryBuilder::new("aaaabbbbcc".to_string()).build();
+        let mut created = memory.clone();
+        created.id = Some(id);
+        created.memory = String::new();
+
+        let mut shell = serde_json::to_value(&memory).unwrap();
+        shell["memory"] = json!("");
+        Mock::given(method("POST"))
+            .and(path("/personality"))
+            .and(body_json(shell))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&created))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for (index, content) in ["aaaa", "bbbb", "cc"].iter().enumerate() {
+            Mock::given(method("POST"))
+                .and(path(format!("/memory/{id}/chunk")))
+                .and(body_json(
+                    json!({ "index": index, "total": 3, "content": content }),
+                ))
+                .respond_with(ResponseTemplate::new(204))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        created.memory = memory.memory.clone();
+        Mock::given(method("POST"))
+            .and(path(format!("/memory/{id}/commit")))
+            .and(body_json(json!({ "total_chunks": 3, "total_bytes": 10 })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let result = Memory::create_chunked(&memory, 4, &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(result.id, Some(id));
+        assert_eq!(result.memory, "aaaabbbbcc");
+    }
+
+    #[tokio::test]
+    async fn test_create_chunked_rejects_zero_chunk_size() {
+        let soulgraph = Soulgraph::new("test-key", "http://127.0.0.1:0");
+        let memory = MemoryBuilder::new("content".to_string()).build();
+        let result = Memory::create_chunked(&memory, 0, &soulgraph).await;
+        assert!(matches!(result, Err(SoulgraphError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_by_time_rejects_inverted_range() {
+        let soulgraph = Soulgraph::new("test-key", "http://127.0.0.1:0");
+        let result = Memory::search_by_time(Some(200), Some(100), &soulgraph).await;
+        assert!(matches!(result, Err(SoulgraphError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_by_time() {
+        let server = MockServer::start().await;
+        let memory = MemoryBuilder::new("in range".to_string())
+            .importance_score(0.5)
+            .build();
+        Mock::given(method("POST"))
+            .and(path("/memory/search"))
+            .and(body_json(json!({ "after": 100, "before": 200 })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([memory])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let memories = Memory::search_by_time(Some(100), Some(200), &soulgraph)
+            .await
+            .unwrap();
+
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].memory, "in range");
+    }
+
+    #[tokio::test]
+    async fn test_find_by_tag() {
+        let server = MockServer::start().await;
+        let memory = MemoryBuilder::new("tagged".to_string())
+            .metadata_builder(|metadata| metadata.add_topic_tag("risk & reward"))
+            .build();
+        Mock::given(method("GET"))
+            .and(path("/memory"))
+            .and(query_param("tag", "risk & reward"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([memory, memory])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let memories = Memory::find_by_tag("risk & reward", &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(memories.len(), 2);
+        assert_eq!(memories[0].metadata.topic_tags, vec!["risk & reward"]);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[0].url.query(), Some("tag=risk+%26+reward"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_consolidate() {
+        let server = MockServer::start().await;
+        let memory_id = Uuid::new_v4();
+        Mock::given(method("POST"))
+            .and(path("/soul/soul-1/memory/consolidate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "memories": {},
+                "indices": {
+                    "by_topic": { "trading": [memory_id] },
+                    "by_type": {}
+                },
+                "stats": {
+                    "total_memories": 3,
+                    "total_fragments": 7,
+                    "average_importance": 0.6,
+                    "last_consolidated": 1736553600000i64
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let graph = Memory::consolidate("soul-1", &Soulgraph::new("test-key", &server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(graph.stats.total_memories, 3);
+        assert_eq!(graph.stats.total_fragments, 7);
+        assert_eq!(graph.stats.average_importance, 0.6);
+        assert_eq!(graph.stats.last_consolidated, Some(1736553600000));
+        assert_eq!(graph.indices.by_topic["trading"], vec![memory_id]);
+    }
+
+    #[tokio::test]
+    async fn test_memory_exists() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/memory/abc"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/memory/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        assert!(Memory::exists("abc", &soulgraph).await.unwrap());
+        assert!(!Memory::exists("missing", &soulgraph).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_get_and_touch() {
+        let stored = Memory {
+            last_accessed: 0,
+            ..MemoryBuilder::new("first trade".to_string()).build()
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&stored))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/memory/abc"))
+            .respond_with(ResponseTemplate::new(204))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/memory/abc"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let before = chrono::Utc::now().timestamp_millis();
+        let touched = Memory::get_and_touch("abc", &soulgraph).await.unwrap();
+        assert_eq!(touched.memory, "first trade");
+        assert!(touched.last_accessed >= before);
+
+        let requests = server.received_requests().await.unwrap();
+        let patch: serde_json::Value = requests[1].body_json().unwrap();
+        assert_eq!(patch, json!({ "last_accessed": touched.last_accessed }));
+
+        // The first PATCH mock is used up, so this touch fails but the get still succeeds.
+        let untouched = Memory::get_and_touch("abc", &soulgraph).await.unwrap();
+        assert_eq!(untouched.last_accessed, 0);
+    }
+
+    #[test]
+    fn test_memory_collection_file_round_trip() {
+        let first = Memory {
+            id: Some(Uuid::new_v4()),
+            ..MemoryBuilder::new("first trade".to_string())
+                .importance_score(0.5)
+                .add_fragment(Fragment::default())
+                .build()
+        };
+        let second = Memory {
+            id: Some(Uuid::new_v4()),
+            ..MemoryBuilder::new("second trade".to_string())
+                .add_weighted_connection(first.id.unwrap(), 0.25)
+                .build()
+        };
+        let collection = MemoryCollection {
+            memories: [first, second]
+                .into_iter()
+                .map(|memory| (memory.id.unwrap(), memory))
+                .collect(),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memories.json");
+
+        collection.to_file(&path).unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        for id in collection.memories.keys() {
+            assert!(written["memories"].get(id.to_string()).is_some());
+        }
+
+        let loaded = MemoryCollection::from_file(&path).unwrap();
+        assert_eq!(loaded.memories.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&collection).unwrap()
+        );
+
+        assert!(matches!(
+            MemoryCollection::from_file(&dir.path().join("missing.json")),
+            Err(SoulgraphError::Io(_))
+        ));
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            MemoryCollection::from_file(&path),
+            Err(SoulgraphError::Decode(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_memory_collection_stream() {
+        let memories: Vec<Memory> = (0..5)
+            .map(|index| MemoryBuilder::new(format!("memory {index}")).build())
+            .collect();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/memory"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&memories))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let streamed: Vec<Memory> = MemoryCollection::stream(&soulgraph)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(streamed.len(), 5);
+        assert_eq!(streamed[0].memory, "memory 0");
+        assert_eq!(streamed[4].memory, "memory 4");
+
+        // Streaming ignores envelope mode.
+        let enveloped = Soulgraph::builder()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .envelope(true)
+            .build();
+        let streamed: Vec<Memory> = MemoryCollection::stream(&enveloped)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(streamed.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_memory_collection_stream_error_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/memory"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let results: Vec<_> = MemoryCollection::stream(&soulgraph).collect().await;
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(SoulgraphError::Status { .. })));
+    }
+
+    #[test]
+    fn test_connections_deserialize_legacy_and_weighted() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let legacy: Memory = serde_json::from_value(json!({
+            "memory": "legacy",
+            "fragments": [],
+            "connections": [first.to_string(), second.to_string()],
+            "emotional_signature": { "valence": 0.0, "intensity": 0.0 },
+            "importance_score": 0.5,
+            "creation_date": 0,
+            "last_accessed": 0,
+            "metadata": { "topic_tags": [], "personality_influence": [], "memory_type": "default" }
+        }))
+        .unwrap();
+        assert_eq!(
+            legacy.connections,
+            vec![Connection::new(first, 1.0), Connection::new(second, 1.0)]
+        );
+
+        let weighted: Vec<Connection> = serde_json::from_value(json!([
+            { "target": first.to_string(), "weight": 0.25 },
+            { "target": second.to_string() }
+        ]))
+        .unwrap();
+        assert_eq!(
+            weighted,
+            vec![Connection::new(first, 0.25), Connection::new(second, 1.0)]
+        );
+        assert_eq!(
+            serde_json::to_value(weighted[0]).unwrap(),
+            json!({ "target": first.to_string(), "weight": 0.25 })
+        );
+
+        assert!(serde_json::from_value::<Connection>(json!("not-a-uuid")).is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let shared = Uuid::new_v4();
+        let only_self = Uuid::new_v4();
+        let only_other = Uuid::new_v4();
+        let id = Uuid::new_v4();
+
+        let mut memory = MemoryBuilder::new("first".to_string())
+            .add_fragment(Fragment::default())
+            .add_weighted_connection(shared, 0.3)
+            .add_connection(only_self)
+            .importance_score(0.4)
+            .emotional_signature(EmotionalSignature {
+                id: None,
+                valence: 0.6,
+                intensity: 0.2,
+            })
+            .metadata_builder(|metadata| metadata.add_topic_tag("trading"))
+            .build();
+        memory.id = Some(id);
+        let other = MemoryBuilder::new("second".to_string())
+            .add_fragment(Fragment::default())
+            .add_fragment(Fragment::default())
+            .add_weighted_connection(shared, 0.8)
+            .add_weighted_connection(only_other, 0.5)
+            .importance_score(0.9)
+            .emotional_signature(EmotionalSignature {
+                id: None,
+                valence: -0.2,
+                intensity: 0.6,
+            })
+            .metadata_builder(|metadata| metadata.add_topic_tag("risk").add_topic_tag("trading"))
+            .build();
+
+        memory.merge(&other);
+
+        assert_eq!(memory.id, Some(id));
+        assert_eq!(memory.memory, "first");
+        assert_eq!(memory.fragments.len(), 3);
+        assert_eq!(
+            memory.connections,
+            vec![
+                Connection::new(shared, 0.8),
+                Connection::new(only_self, 1.0),
+                Connection::new(only_other, 0.5),
+            ]
+        );
+        assert_eq!(memory.importance_score, 0.9);
+        approx::assert_relative_eq!(memory.emotional_signature.valence, 0.2);
+        approx::assert_relative_eq!(memory.emotional_signature.intensity, 0.4);
+        assert_eq!(memory.metadata.topic_tags, vec!["trading", "risk"]);
+    }
+
+    #[test]
+    fn test_apply_json_patch() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let mut memory = MemoryBuilder::new("patched".to_string())
+            .add_connection(first)
+            .add_connection(second)
+            .importance_score(0.2)
+            .build();
+
+        memory
+            .apply_json_patch(&json!([
+                { "op": "replace", "path": "/importance_score", "value": 0.75 },
+                { "op": "remove", "path": "/connections/0" }
+            ]))
+            .unwrap();
+        assert_eq!(memory.importance_score, 0.75);
+        assert_eq!(memory.connections, vec![Connection::new(second, 1.0)]);
+
+        let err = memory
+            .apply_json_patch(&json!([
+                { "op": "replace", "path": "/importance_score", "value": 0.1 },
+                { "op": "remove", "path": "/connections/4" }
+            ]))
+            .unwrap_err();
+        assert!(matches!(err, PatchError::InvalidPath(path) if path == "/connections/4"));
+        assert_eq!(memory.importance_score, 0.75);
+
+        let err = memory
+            .apply_json_patch(&json!([
+                { "op": "replace", "path": "/importance_score", "value": 1.5 }
+            ]))
+            .unwrap_err();
+        assert!(matches!(err, PatchError::Invalid(_)));
+        assert_eq!(memory.importance_score, 0.75);
+    }
+
+    #[test]
+    fn test_apply_json_patch_keeps_untouched_scores_exact() {
+        let mut memory = MemoryBuilder::new("precise".to_string())
+            .add_fragment(Fragment {
+                importance: 0.555_555,
+                emotional_valence: -0.123_456_7,
+                ..Fragment::default()
+            })
+            .importance_score(0.123_456)
+            .build();
+        memory.connections = vec![Connection::new(Uuid::new_v4(), 0.987_654_3)];
+        memory.emotional_signature.valence = -0.333_333;
+        memory.emotional_signature.intensity = 0.000_012_3;
+        let original = memory.clone();
+
+        memory
+            .apply_json_patch(&json!([
+                { "op": "replace", "path": "/memory", "value": "renamed" }
+            ]))
+            .unwrap();
+
+        assert_eq!(memory.memory, "renamed");
+        assert_eq!(memory.importance_score, original.importance_score);
+        assert_eq!(memory.connections, original.connections);
+        assert_eq!(
+            memory.emotional_signature.valence,
+            original.emotional_signature.valence
+        );
+        assert_eq!(
+            memory.emotional_signature.intensity,
+            original.emotional_signature.intensity
+        );
+        assert_eq!(memory.fragments[0].importance, 0.555_555);
+        assert_eq!(memory.fragments[0].emotional_valence, -0.123_456_7);
+    }
+
+    #[test]
+    fn test_derive_emotional_signature() {
+        let fragment = |importance, emotional_valence| Fragment {
+            importance,
+            emotional_valence,
+            ..Fragment::default()
+        };
+        let mut memory = MemoryBuilder::new("mixed day".to_string())
+            .add_fragment(fragment(0.75, 0.8))
+            .add_fragment(fragment(0.25, -0.4))
+            .build();
+
+        memory.derive_emotional_signature();
+        approx::assert_relative_eq!(memory.emotional_signature.valence, 0.5);
+        approx::assert_relative_eq!(memory.emotional_signature.intensity, 0.5);
+
+        let mut unweighted = MemoryBuilder::new("flat".to_string())
+            .add_fragment(fragment(0.0, 0.6))
+            .add_fragment(fragment(0.0, -0.2))
+            .build();
+        unweighted.derive_emotional_signature();
+        approx::assert_relative_eq!(unweighted.emotional_signature.valence, 0.2);
+        approx::assert_relative_eq!(unweighted.emotional_signature.intensity, 0.0);
+    }
+
+    #[test]
+    fn test_derive_emotional_signature_without_fragments() {
+        let mut memory = MemoryBuilder::new("empty".to_string())
+            .emotional_signature(EmotionalSignature {
+                id: None,
+                valence: -0.3,
+                intensity: 0.9,
+            })
+            .build();
+
+        memory.derive_emotional_signature();
+        assert_eq!(memory.emotional_signature.valence, -0.3);
+        assert_eq!(memory.emotional_signature.intensity, 0.9);
+    }
+
+    #[test]
+    fn test_strongest_connections() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let memory = MemoryBuilder::new("linked".to_string())
+            .add_weighted_connection(ids[0], 0.2)
+            .add_weighted_connection(ids[1], 0.9)
+            .add_connection(ids[2])
+            .add_weighted_connection(ids[3], 0.9)
+            .build();
+
+        let strongest: Vec<Uuid> = memory
+            .strongest_connections(3)
+            .iter()
+            .map(|connection| connection.target)
+            .collect();
+        assert_eq!(strongest, vec![ids[2], ids[1], ids[3]]);
+        assert_eq!(memory.strongest_connections(10).len(), 4);
+        assert!(memory.strongest_connections(0).is_empty());
+    }
+
+    fn memory_with_emotion(importance: f32, valence: f32, intensity: f32) -> Memory {
+        MemoryBuilder::new("felt something".to_string())
+            .importance_score(importance)
+            .emotional_signature(EmotionalSignature {
+                id: None,
+                valence,
+                intensity,
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_mood_quadrants() {
+        let mood = |valence, intensity| {
+            EmotionalSignature {
+                id: None,
+                valence,
+                intensity,
+            }
+            .mood()
+        };
+
+        assert_eq!(mood(0.0, 0.0), Mood::Neutral);
+        assert_eq!(mood(0.19, 0.9), Mood::Neutral);
+        assert_eq!(mood(-0.19, 0.1), Mood::Neutral);
+        assert_eq!(mood(0.8, 0.9), Mood::Joyful);
+        assert_eq!(mood(0.2, 0.5), Mood::Joyful);
+        assert_eq!(mood(0.6, 0.2), Mood::Content);
+        assert_eq!(mood(-0.4, 0.8), Mood::Anxious);
+        assert_eq!(mood(-0.6, 0.5), Mood::Angry);
+        assert_eq!(mood(-0.9, 1.0), Mood::Angry);
+        assert_eq!(mood(-0.7, 0.3), Mood::Sad);
+        assert_eq!(EmotionalSignature::default().mood(), Mood::Neutral);
+    }
+
+    #[test]
+    fn test_average_emotion_is_importance_weighted() {
+        let collection: MemoryCollection = vec![
+            memory_with_emotion(0.75, 1.0, 0.2),
+            memory_with_emotion(0.25, -1.0, 0.6),
+        ]
+        .into_iter()
+        .collect();
+
+        let average = collection.average_emotion().unwrap();
+        approx::assert_relative_eq!(average.valence, 0.5);
+        approx::assert_relative_eq!(average.intensity, 0.3);
+
+        let unweighted: MemoryCollection = vec![
+            memory_with_emotion(0.0, 1.0, 0.2),
+            memory_with_emotion(0.0, -0.5, 0.6),
+        ]
+        .into_iter()
+        .collect();
+        let average = unweighted.average_emotion().unwrap();
+        approx::assert_relative_eq!(average.valence, 0.25);
+        approx::assert_relative_eq!(average.intensity, 0.4);
+    }
+
+    #[test]
+    fn test_average_emotion_of_empty_collection() {
+        let collection: MemoryCollection = Vec::new().into_iter().collect();
+        assert!(collection.average_emotion().is_none());
+    }
+
     #[test]
     fn test_serde() {
         let memory = MemoryBuilder::new("test memory".to_string())
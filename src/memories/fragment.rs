@@ -1,20 +1,40 @@
-use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum FragmentType {
     #[default]
     Observation,
     Reflection,
+    /// A consolidated memory.
+    Memory,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Context {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub topic: String,
     pub user_state: String,
+    #[serde(default = "default_agent_state")]
+    pub agent_state: String,
+    #[serde(default = "default_interaction_type")]
+    pub interaction_type: String,
+}
+
+fn default_agent_state() -> String {
+    "neutral".to_string()
+}
+
+fn default_interaction_type() -> String {
+    "conversation".to_string()
 }
 
 impl Default for Context {
@@ -23,25 +43,180 @@ impl Default for Context {
             id: None,
             topic: "general".to_string(),
             user_state: "neutral".to_string(),
+            agent_state: default_agent_state(),
+            interaction_type: default_interaction_type(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Builds a [`Context`]; unset fields take their [`Context::default`] values.
+#[derive(Default)]
+pub struct ContextBuilder {
+    topic: Option<String>,
+    user_state: Option<String>,
+    agent_state: Option<String>,
+    interaction_type: Option<String>,
+}
+
+impl ContextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    #[must_use]
+    pub fn user_state(mut self, user_state: impl Into<String>) -> Self {
+        self.user_state = Some(user_state.into());
+        self
+    }
+
+    #[must_use]
+    pub fn agent_state(mut self, agent_state: impl Into<String>) -> Self {
+        self.agent_state = Some(agent_state.into());
+        self
+    }
+
+    #[must_use]
+    pub fn interaction_type(mut self, interaction_type: impl Into<String>) -> Self {
+        self.interaction_type = Some(interaction_type.into());
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Context {
+        let defaults = Context::default();
+        Context {
+            id: None,
+            topic: self.topic.unwrap_or(defaults.topic),
+            user_state: self.user_state.unwrap_or(defaults.user_state),
+            agent_state: self.agent_state.unwrap_or(defaults.agent_state),
+            interaction_type: self.interaction_type.unwrap_or(defaults.interaction_type),
+        }
+    }
+}
+
+/// A single observation or reflection within a memory.
+///
+/// Serializes flat, with the fragment type under `"type"`. With the `adjacent-fragments`
+/// feature it serializes adjacently tagged instead (`{"type": ..., "fragment": {...}}`).
+/// Deserialization accepts both shapes regardless of the feature.
+#[derive(Debug, Clone)]
 pub struct Fragment {
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<Uuid>,
-    #[serde(rename = "type")]
     pub fragment_type: FragmentType,
     pub content: String,
     pub timestamp: i64,
-    #[serde(with = "validate_importance")]
     pub importance: f32,
-    #[serde(with = "validate_emotional_valence")]
     pub emotional_valence: f32,
     pub context: Context,
 }
 
+/// Every `Fragment` field except its type.
+#[derive(Serialize, Deserialize)]
+struct FragmentBody {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
+    id: Option<Uuid>,
+    content: String,
+    timestamp: i64,
+    #[serde(with = "validate_importance")]
+    importance: f32,
+    #[serde(with = "validate_emotional_valence")]
+    emotional_valence: f32,
+    context: Context,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FlatFragment {
+    #[serde(rename = "type")]
+    fragment_type: FragmentType,
+    #[serde(flatten)]
+    body: FragmentBody,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AdjacentFragment {
+    #[serde(rename = "type")]
+    fragment_type: FragmentType,
+    fragment: FragmentBody,
+}
+
+impl Fragment {
+    fn split(&self) -> (FragmentType, FragmentBody) {
+        let body = FragmentBody {
+            id: self.id,
+            content: self.content.clone(),
+            timestamp: self.timestamp,
+            importance: self.importance,
+            emotional_valence: self.emotional_valence,
+            context: self.context.clone(),
+        };
+        (self.fragment_type.clone(), body)
+    }
+
+    fn join(fragment_type: FragmentType, body: FragmentBody) -> Self {
+        Self {
+            id: body.id,
+            fragment_type,
+            content: body.content,
+            timestamp: body.timestamp,
+            importance: body.importance,
+            emotional_valence: body.emotional_valence,
+            context: body.context,
+        }
+    }
+}
+
+impl Serialize for Fragment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (fragment_type, body) = self.split();
+        if cfg!(feature = "adjacent-fragments") {
+            AdjacentFragment {
+                fragment_type,
+                fragment: body,
+            }
+            .serialize(serializer)
+        } else {
+            FlatFragment {
+                fragment_type,
+                body,
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Fragment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let fragment = if value
+            .get("fragment")
+            .is_some_and(serde_json::Value::is_object)
+        {
+            AdjacentFragment::deserialize(value)
+                .map(|adjacent| Fragment::join(adjacent.fragment_type, adjacent.fragment))
+        } else {
+            FlatFragment::deserialize(value)
+                .map(|flat| Fragment::join(flat.fragment_type, flat.body))
+        };
+        fragment.map_err(de::Error::custom)
+    }
+}
+
 mod validate_importance {
     use serde::{Deserialize, Deserializer, Serializer};
 
@@ -49,7 +224,7 @@ mod validate_importance {
     where
         S: Serializer,
     {
-        serializer.serialize_f32(*importance)
+        crate::precision::serialize(importance, serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<f32, D::Error>
@@ -73,7 +248,7 @@ mod validate_emotional_valence {
     where
         S: Serializer,
     {
-        serializer.serialize_f32(*valence)
+        crate::precision::serialize(valence, serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<f32, D::Error>
@@ -118,6 +293,28 @@ impl Fragment {
     pub fn builder() -> FragmentBuilder {
         FragmentBuilder::default()
     }
+
+    /// Orders fragments chronologically by `timestamp`.
+    pub fn cmp_by_time(&self, other: &Fragment) -> Ordering {
+        self.timestamp.cmp(&other.timestamp)
+    }
+
+    /// Whether the two fragments say the same thing: every field matches except the ids
+    /// (of the fragment and its context) and `timestamp`.
+    ///
+    /// Useful for matching locally built fragments against server copies, or for dropping
+    /// duplicates recorded at different times.
+    pub fn content_eq(&self, other: &Fragment) -> bool {
+        let (a, b) = (&self.context, &other.context);
+        self.fragment_type == other.fragment_type
+            && self.content == other.content
+            && self.importance == other.importance
+            && self.emotional_valence == other.emotional_valence
+            && a.topic == b.topic
+            && a.user_state == b.user_state
+            && a.agent_state == b.agent_state
+            && a.interaction_type == b.interaction_type
+    }
 }
 impl FragmentBuilder {
     pub fn new(fragment_type: FragmentType, content: String) -> Self {
@@ -127,36 +324,49 @@ impl FragmentBuilder {
             ..Default::default()
         }
     }
+    #[must_use]
     pub fn fragment_type(mut self, fragment_type: FragmentType) -> Self {
         self.fragment_type = fragment_type;
         self
     }
 
+    #[must_use]
     pub fn content(mut self, content: String) -> Self {
         self.content = content;
         self
     }
 
+    #[must_use]
     pub fn timestamp(mut self, timestamp: i64) -> Self {
         self.timestamp = Some(timestamp);
         self
     }
 
+    #[must_use]
     pub fn importance(mut self, importance: f32) -> Self {
         self.importance = importance;
         self
     }
 
+    #[must_use]
     pub fn emotional_valence(mut self, valence: f32) -> Self {
         self.emotional_valence = valence;
         self
     }
 
+    #[must_use]
     pub fn context(mut self, context: Context) -> Self {
         self.context = Some(context);
         self
     }
 
+    /// Builds the context inline, e.g. `.with_context(|cb| cb.topic("trading"))`.
+    #[must_use]
+    pub fn with_context(self, configure: impl FnOnce(ContextBuilder) -> ContextBuilder) -> Self {
+        self.context(configure(ContextBuilder::new()).build())
+    }
+
+    #[must_use]
     pub fn build(self) -> Fragment {
         Fragment {
             id: None,
@@ -167,11 +377,7 @@ impl FragmentBuilder {
                 .unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
             importance: self.importance,
             emotional_valence: self.emotional_valence,
-            context: self.context.unwrap_or_else(|| Context {
-                id: None,
-                topic: "general".to_string(),
-                user_state: "neutral".to_string(),
-            }),
+            context: self.context.unwrap_or_default(),
         }
     }
 }
@@ -181,6 +387,32 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_content_eq_ignores_ids_and_timestamp() {
+        let local = FragmentBuilder::new(FragmentType::Reflection, "went well".to_string())
+            .timestamp(1_000)
+            .importance(0.6)
+            .build();
+        let mut stored = local.clone();
+        stored.id = Some(Uuid::new_v4());
+        stored.context.id = Some(Uuid::new_v4());
+        stored.timestamp = 2_000;
+        assert!(local.content_eq(&stored));
+        assert!(stored.content_eq(&local));
+
+        let mut reworded = stored.clone();
+        reworded.content = "went badly".to_string();
+        assert!(!local.content_eq(&reworded));
+
+        let mut observed = stored.clone();
+        observed.fragment_type = FragmentType::Observation;
+        assert!(!local.content_eq(&observed));
+
+        let mut other_topic = stored;
+        other_topic.context.topic = "trading".to_string();
+        assert!(!local.content_eq(&other_topic));
+    }
+
     #[test]
     fn test_fragment_type_default() {
         assert!(matches!(FragmentType::default(), FragmentType::Observation));
@@ -191,6 +423,31 @@ mod tests {
         let context = Context::default();
         assert_eq!(context.topic, "general");
         assert_eq!(context.user_state, "neutral");
+        assert_eq!(context.agent_state, "neutral");
+        assert_eq!(context.interaction_type, "conversation");
+    }
+
+    #[test]
+    fn test_context_without_agent_fields_deserializes() {
+        let context: Context =
+            serde_json::from_value(json!({ "topic": "trading", "user_state": "anxious" })).unwrap();
+        assert_eq!(context.topic, "trading");
+        assert_eq!(context.agent_state, "neutral");
+        assert_eq!(context.interaction_type, "conversation");
+
+        let context: Context = serde_json::from_value(json!({
+            "topic": "trading",
+            "user_state": "anxious",
+            "agent_state": "focused",
+            "interaction_type": "task"
+        }))
+        .unwrap();
+        assert_eq!(context.agent_state, "focused");
+        assert_eq!(context.interaction_type, "task");
+        assert_eq!(
+            serde_json::to_value(&context).unwrap()["agent_state"],
+            "focused"
+        );
     }
 
     #[test]
@@ -214,6 +471,7 @@ mod tests {
                 id: None,
                 topic: "test".to_string(),
                 user_state: "happy".to_string(),
+                ..Context::default()
             })
             .build();
 
@@ -226,6 +484,33 @@ mod tests {
         assert_eq!(fragment.context.user_state, "happy");
     }
 
+    #[test]
+    fn test_fragment_builder_inline_context() {
+        let fragment = FragmentBuilder::new(FragmentType::Observation, "Test".to_string())
+            .with_context(|context| {
+                context
+                    .topic("trading")
+                    .user_state("anxious")
+                    .agent_state("reassuring")
+                    .interaction_type("support")
+            })
+            .build();
+        assert_eq!(fragment.context.topic, "trading");
+        assert_eq!(fragment.context.user_state, "anxious");
+        assert_eq!(fragment.context.agent_state, "reassuring");
+        assert_eq!(fragment.context.interaction_type, "support");
+
+        let fragment = FragmentBuilder::new(FragmentType::Observation, "Test".to_string())
+            .with_context(|context| context.topic("trading"))
+            .build();
+        assert_eq!(fragment.context.topic, "trading");
+        assert_eq!(fragment.context.user_state, "neutral");
+
+        let context = ContextBuilder::new().build();
+        assert_eq!(context.topic, "general");
+        assert_eq!(context.user_state, "neutral");
+    }
+
     #[test]
     fn test_fragment_serialization() {
         let fragment =
@@ -244,6 +529,78 @@ mod tests {
         assert_eq!(deserialized.importance, 0.7);
     }
 
+    #[test]
+    fn test_fragment_deserializes_both_representations() {
+        let flat = json!({
+            "type": "reflection",
+            "content": "flat",
+            "timestamp": 1,
+            "importance": 0.5,
+            "emotional_valence": -0.5,
+            "context": { "topic": "trading", "user_state": "anxious" }
+        });
+        let adjacent = json!({
+            "type": "reflection",
+            "fragment": {
+                "content": "adjacent",
+                "timestamp": 1,
+                "importance": 0.5,
+                "emotional_valence": -0.5,
+                "context": { "topic": "trading", "user_state": "anxious" }
+            }
+        });
+
+        let flat: Fragment = serde_json::from_value(flat).unwrap();
+        let adjacent: Fragment = serde_json::from_value(adjacent).unwrap();
+
+        assert!(matches!(flat.fragment_type, FragmentType::Reflection));
+        assert!(matches!(adjacent.fragment_type, FragmentType::Reflection));
+        assert_eq!(flat.content, "flat");
+        assert_eq!(adjacent.content, "adjacent");
+        assert_eq!(adjacent.context.topic, "trading");
+    }
+
+    #[test]
+    fn test_fragment_deserialization_keeps_range_errors() {
+        let err = serde_json::from_value::<Fragment>(json!({
+            "type": "observation",
+            "content": "too important",
+            "timestamp": 1,
+            "importance": 2.0,
+            "emotional_valence": 0.0,
+            "context": { "topic": "general", "user_state": "neutral" }
+        }))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("importance must be between 0 and 1"));
+    }
+
+    #[cfg(not(feature = "adjacent-fragments"))]
+    #[test]
+    fn test_fragment_serializes_flat() {
+        let fragment = FragmentBuilder::new(FragmentType::Reflection, "flat".to_string()).build();
+        let value = serde_json::to_value(&fragment).unwrap();
+
+        assert_eq!(value["type"], "reflection");
+        assert_eq!(value["content"], "flat");
+        assert!(value.get("fragment").is_none());
+    }
+
+    #[cfg(feature = "adjacent-fragments")]
+    #[test]
+    fn test_fragment_serializes_adjacently_tagged() {
+        let fragment = FragmentBuilder::new(FragmentType::Reflection, "tagged".to_string()).build();
+        let value = serde_json::to_value(&fragment).unwrap();
+
+        assert_eq!(value["type"], "reflection");
+        assert_eq!(value["fragment"]["content"], "tagged");
+        assert!(value.get("content").is_none());
+
+        let deserialized: Fragment = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.content, "tagged");
+    }
+
     #[test]
     fn test_fragment_type_serde() {
         let json_obs = json!("observation");
@@ -262,6 +619,27 @@ mod tests {
         assert_eq!(serialized_ref, "\"reflection\"");
     }
 
+    #[test]
+    fn test_fragment_type_memory_serde() {
+        assert_eq!(
+            serde_json::to_value(FragmentType::Memory).unwrap(),
+            json!("memory")
+        );
+        assert_eq!(
+            serde_json::from_value::<FragmentType>(json!("memory")).unwrap(),
+            FragmentType::Memory
+        );
+
+        let fragment = Fragment {
+            fragment_type: FragmentType::Memory,
+            ..Fragment::default()
+        };
+        let value = serde_json::to_value(&fragment).unwrap();
+        assert_eq!(value["type"], "memory");
+        let back: Fragment = serde_json::from_value(value).unwrap();
+        assert_eq!(back.fragment_type, FragmentType::Memory);
+    }
+
     #[test]
     fn test_builder_default() {
         let builder = FragmentBuilder::default();
@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+
+use futures::stream::{self, Stream, StreamExt};
+use serde::de::{DeserializeOwned, Error as _};
+
+use crate::SoulgraphError;
+
+/// Incrementally splits a top-level JSON array into the raw bytes of its elements.
+#[derive(Debug, Default)]
+struct ArraySplitter {
+    element: Vec<u8>,
+    started: bool,
+    finished: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl ArraySplitter {
+    /// Feeds `chunk`, pushing every element it completes onto `out`.
+    fn push(&mut self, chunk: &[u8], out: &mut VecDeque<Vec<u8>>) -> Result<(), SoulgraphError> {
+        for &byte in chunk {
+            if self.finished {
+                if !byte.is_ascii_whitespace() {
+                    return Err(malformed("trailing data after the array"));
+                }
+                continue;
+            }
+            if !self.started {
+                match byte {
+                    b'[' => self.started = true,
+                    byte if byte.is_ascii_whitespace() => {}
+                    _ => return Err(malformed("expected a JSON array")),
+                }
+                continue;
+            }
+            if self.in_string {
+                self.element.push(byte);
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+            match byte {
+                b',' if self.depth == 0 => out.push_back(std::mem::take(&mut self.element)),
+                b']' if self.depth == 0 => {
+                    if !self.element.iter().all(u8::is_ascii_whitespace) {
+                        out.push_back(std::mem::take(&mut self.element));
+                    }
+                    self.finished = true;
+                }
+                _ => {
+                    match byte {
+                        b'"' => self.in_string = true,
+                        b'{' | b'[' => self.depth += 1,
+                        b'}' | b']' => {
+                            self.depth = self
+                                .depth
+                                .checked_sub(1)
+                                .ok_or_else(|| malformed("unbalanced closing bracket"))?;
+                        }
+                        _ => {}
+                    }
+                    self.element.push(byte);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails unless the closing `]` of the array has been seen.
+    fn finish(&self) -> Result<(), SoulgraphError> {
+        if self.finished {
+            Ok(())
+        } else {
+            Err(malformed("unexpected end of array"))
+        }
+    }
+}
+
+fn malformed(reason: &str) -> SoulgraphError {
+    SoulgraphError::Decode(serde_json::Error::custom(reason))
+}
+
+struct State<S> {
+    body: Option<S>,
+    splitter: ArraySplitter,
+    pending: VecDeque<Vec<u8>>,
+}
+
+/// Decodes the elements of a JSON array as `body` delivers it, one element at a time.
+///
+/// Only the bytes of the element being parsed are held, never the whole array. The stream
+/// ends after the first error.
+pub(crate) fn elements<T, S, B, E>(body: S) -> impl Stream<Item = Result<T, SoulgraphError>>
+where
+    T: DeserializeOwned,
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<SoulgraphError>,
+{
+    let state = State {
+        body: Some(Box::pin(body)),
+        splitter: ArraySplitter::default(),
+        pending: VecDeque::new(),
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(raw) = state.pending.pop_front() {
+                let element = serde_json::from_slice(&raw).map_err(SoulgraphError::from);
+                if element.is_err() {
+                    state.body = None;
+                    state.pending.clear();
+                }
+                return Some((element, state));
+            }
+            let body = state.body.as_mut()?;
+            let result = match body.next().await {
+                Some(Ok(chunk)) => state.splitter.push(chunk.as_ref(), &mut state.pending),
+                Some(Err(err)) => Err(err.into()),
+                None => {
+                    state.body = None;
+                    state.splitter.finish()
+                }
+            };
+            if let Err(err) = result {
+                state.body = None;
+                state.pending.clear();
+                return Some((Err(err), state));
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect_bytewise(json: &str) -> Vec<Result<serde_json::Value, SoulgraphError>> {
+        let chunks = json
+            .as_bytes()
+            .iter()
+            .map(|&byte| Ok::<_, SoulgraphError>(vec![byte]));
+        elements(stream::iter(chunks)).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_elements_split_across_chunks() {
+        let items = collect_bytewise(r#" [ {"a": "x]\"}"}, [1, 2], "s,t" , 3 ] "#).await;
+        let items: Vec<_> = items.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            items,
+            vec![
+                serde_json::json!({ "a": "x]\"}" }),
+                serde_json::json!([1, 2]),
+                serde_json::json!("s,t"),
+                serde_json::json!(3),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_array() {
+        assert!(collect_bytewise("[]").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_array_is_an_error() {
+        let items = collect_bytewise(r#"[1, 2"#).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), &serde_json::json!(1));
+        assert!(matches!(items[1], Err(SoulgraphError::Decode(_))));
+    }
+
+    #[tokio::test]
+    async fn test_non_array_is_an_error() {
+        let items = collect_bytewise(r#"{"memories": {}}"#).await;
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(SoulgraphError::Decode(_))));
+    }
+}
@@ -0,0 +1,191 @@
+//! The `add`, `replace` and `remove` operations of JSON Patch (RFC 6902).
+
+use serde_json::Value;
+
+/// Why [`Memory::apply_json_patch`](super::Memory::apply_json_patch) failed.
+#[derive(Debug)]
+pub enum PatchError {
+    /// The patch is not an array of operation objects with `op`, `path` and, where
+    /// required, `value`.
+    Malformed(String),
+    /// The operation is valid JSON Patch but not supported, e.g. `move`.
+    UnsupportedOp(String),
+    /// The path does not point at an existing location (or its parent, for `add`).
+    InvalidPath(String),
+    /// The patched document is no longer a valid memory, e.g. a score out of range.
+    Invalid(serde_json::Error),
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchError::Malformed(reason) => write!(f, "malformed patch: {reason}"),
+            PatchError::UnsupportedOp(op) => write!(f, "unsupported patch operation \"{op}\""),
+            PatchError::InvalidPath(path) => write!(f, "no location at path \"{path}\""),
+            PatchError::Invalid(err) => write!(f, "patched memory is invalid: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PatchError::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Applies every operation of `patch` to `document` in order.
+pub(super) fn apply(document: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    let operations = patch
+        .as_array()
+        .ok_or_else(|| PatchError::Malformed("expected an array of operations".to_string()))?;
+    for operation in operations {
+        let field = |name: &str| {
+            operation
+                .get(name)
+                .ok_or_else(|| PatchError::Malformed(format!("operation is missing \"{name}\"")))
+        };
+        let op = field("op")?
+            .as_str()
+            .ok_or_else(|| PatchError::Malformed("\"op\" must be a string".to_string()))?;
+        let path = field("path")?
+            .as_str()
+            .ok_or_else(|| PatchError::Malformed("\"path\" must be a string".to_string()))?;
+        match op {
+            "add" => add(document, path, field("value")?.clone())?,
+            "replace" => replace(document, path, field("value")?.clone())?,
+            "remove" => remove(document, path)?,
+            other => return Err(PatchError::UnsupportedOp(other.to_string())),
+        }
+    }
+    Ok(())
+}
+
+/// Splits `path` into the parent's JSON pointer and the unescaped last token.
+fn split(path: &str) -> Result<(&str, String), PatchError> {
+    let (parent, token) = path
+        .rsplit_once('/')
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+fn parent<'a>(document: &'a mut Value, path: &str) -> Result<(&'a mut Value, String), PatchError> {
+    let (parent, token) = split(path)?;
+    let parent = document
+        .pointer_mut(parent)
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    Ok((parent, token))
+}
+
+fn index(token: &str, len: usize, path: &str) -> Result<usize, PatchError> {
+    token
+        .parse::<usize>()
+        .ok()
+        .filter(|&index| index < len)
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    match parent(document, path)? {
+        (Value::Object(map), key) => {
+            map.insert(key, value);
+        }
+        (Value::Array(items), token) if token == "-" => items.push(value),
+        (Value::Array(items), token) => {
+            // Inserting at `len` appends, so the index may be one past the end.
+            let at = index(&token, items.len() + 1, path)?;
+            items.insert(at, value);
+        }
+        _ => return Err(PatchError::InvalidPath(path.to_string())),
+    }
+    Ok(())
+}
+
+fn replace(document: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    let target = document
+        .pointer_mut(path)
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    *target = value;
+    Ok(())
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<(), PatchError> {
+    let removed = match parent(document, path)? {
+        (Value::Object(map), key) => map.remove(&key).is_some(),
+        (Value::Array(items), token) => {
+            items.remove(index(&token, items.len(), path)?);
+            true
+        }
+        _ => false,
+    };
+    if removed {
+        Ok(())
+    } else {
+        Err(PatchError::InvalidPath(path.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_add_replace_remove() {
+        let mut document = json!({ "a": { "b/c": 1 }, "list": [1, 2] });
+        apply(
+            &mut document,
+            &json!([
+                { "op": "add", "path": "/list/1", "value": 9 },
+                { "op": "add", "path": "/list/-", "value": 3 },
+                { "op": "add", "path": "/new", "value": true },
+                { "op": "replace", "path": "/a/b~1c", "value": 2 },
+                { "op": "remove", "path": "/list/0" }
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            document,
+            json!({ "a": { "b/c": 2 }, "list": [9, 2, 3], "new": true })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut document = json!({ "list": [1] });
+        assert!(matches!(
+            apply(&mut document, &json!({ "op": "add" })),
+            Err(PatchError::Malformed(_))
+        ));
+        assert!(matches!(
+            apply(&mut document, &json!([{ "op": "replace", "path": "/x" }])),
+            Err(PatchError::Malformed(_))
+        ));
+        assert!(matches!(
+            apply(
+                &mut document,
+                &json!([{ "op": "move", "from": "/list", "path": "/y" }])
+            ),
+            Err(PatchError::UnsupportedOp(op)) if op == "move"
+        ));
+        for path in ["/missing", "/list/1", "/list/x", "list"] {
+            assert!(matches!(
+                apply(&mut document, &json!([{ "op": "remove", "path": path }])),
+                Err(PatchError::InvalidPath(_))
+            ));
+        }
+        assert!(matches!(
+            apply(
+                &mut document,
+                &json!([{ "op": "add", "path": "/list/5", "value": 0 }])
+            ),
+            Err(PatchError::InvalidPath(_))
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+//! Hooks run around every request sent by a [`Soulgraph`](crate::Soulgraph) client.
+
+use std::fmt;
+
+use futures::future::BoxFuture;
+use reqwest::{Request, Response};
+
+/// Custom behavior around each request, such as signing or metrics.
+///
+/// Registered with [`SoulgraphBuilder::middleware`](crate::SoulgraphBuilder::middleware) and
+/// run in registration order. Both hooks default to doing nothing. Requests captured in
+/// dry-run mode are never sent, so no hook runs for them.
+pub trait RequestMiddleware: Send + Sync {
+    /// Called with the fully built request just before it is sent.
+    fn before<'a>(&'a self, request: &'a mut Request) -> BoxFuture<'a, ()> {
+        let _ = request;
+        Box::pin(async {})
+    }
+
+    /// Called with the response as soon as it arrives, before its status is checked.
+    fn after<'a>(&'a self, response: &'a Response) -> BoxFuture<'a, ()> {
+        let _ = response;
+        Box::pin(async {})
+    }
+}
+
+impl fmt::Debug for dyn RequestMiddleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestMiddleware")
+    }
+}
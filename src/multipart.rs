@@ -0,0 +1,118 @@
+use reqwest::multipart::{Form, Part};
+
+use crate::SoulgraphError;
+
+/// One field of a [`Soulgraph::post_multipart`](crate::Soulgraph::post_multipart) upload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MultipartPart {
+    Text {
+        name: String,
+        value: String,
+    },
+    File {
+        name: String,
+        bytes: Vec<u8>,
+        filename: String,
+        mime: String,
+    },
+}
+
+impl MultipartPart {
+    pub fn text(name: impl Into<String>, value: impl Into<String>) -> Self {
+        MultipartPart::Text {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// A file field; `mime` must parse as a media type such as `"audio/wav"`.
+    pub fn file(
+        name: impl Into<String>,
+        bytes: impl Into<Vec<u8>>,
+        filename: impl Into<String>,
+        mime: impl Into<String>,
+    ) -> Self {
+        MultipartPart::File {
+            name: name.into(),
+            bytes: bytes.into(),
+            filename: filename.into(),
+            mime: mime.into(),
+        }
+    }
+}
+
+pub(crate) fn form(parts: Vec<MultipartPart>) -> Result<Form, SoulgraphError> {
+    parts.into_iter().try_fold(Form::new(), |form, part| {
+        Ok(match part {
+            MultipartPart::Text { name, value } => form.text(name, value),
+            MultipartPart::File {
+                name,
+                bytes,
+                filename,
+                mime,
+            } => form.part(
+                name,
+                Part::bytes(bytes).file_name(filename).mime_str(&mime)?,
+            ),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Soulgraph;
+    use wiremock::{
+        matchers::{header_regex, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_post_multipart_sends_text_and_file_parts() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/memory/abc/attachments"))
+            .and(header_regex(
+                "content-type",
+                "^multipart/form-data; boundary=",
+            ))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let response = soulgraph
+            .post_multipart(
+                "/memory/abc/attachments",
+                vec![
+                    MultipartPart::text("caption", "first session"),
+                    MultipartPart::file("audio", b"RIFF0000".to_vec(), "clip.wav", "audio/wav"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("Content-Disposition: form-data; name=\"caption\""));
+        assert!(body.contains("first session"));
+        assert!(
+            body.contains("Content-Disposition: form-data; name=\"audio\"; filename=\"clip.wav\"")
+        );
+        assert!(body.contains("Content-Type: audio/wav"));
+        assert!(body.contains("RIFF0000"));
+    }
+
+    #[test]
+    fn test_invalid_mime_is_rejected() {
+        let result = form(vec![MultipartPart::file(
+            "audio",
+            vec![0],
+            "clip",
+            "not a mime",
+        )]);
+        assert!(matches!(result, Err(SoulgraphError::Http(_))));
+    }
+}
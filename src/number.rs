@@ -0,0 +1,86 @@
+//! Lenient deserialization of `f32` scores.
+//!
+//! Some stores emit numeric fields as strings (`"strength": "0.9"`). Scores are accepted
+//! either way and always serialized back as numbers.
+
+use serde::{de, Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Number {
+    Number(f32),
+    String(String),
+}
+
+/// Accepts a number or a string holding one.
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Number::deserialize(deserializer)? {
+        Number::Number(number) => Ok(number),
+        Number::String(string) => string
+            .trim()
+            .parse()
+            .map_err(|_| de::Error::custom(format!("invalid number string {string:?}"))),
+    }
+}
+
+/// Like [`deserialize`], but rejects values outside `0..=1`.
+pub(crate) fn deserialize_unit<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let number = deserialize(deserializer)?;
+    if !(0.0..=1.0).contains(&number) {
+        return Err(de::Error::custom(format!(
+            "{number} is outside the range 0..=1"
+        )));
+    }
+    Ok(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{memories::Memory, personality::traits::Trait, personality::value::Value};
+    use serde_json::json;
+
+    #[test]
+    fn test_numbers_and_numeric_strings() {
+        for strength in [json!(0.9), json!("0.9"), json!(" 0.9 ")] {
+            let trait_: Trait =
+                serde_json::from_value(json!({ "trait": "bold", "strength": strength })).unwrap();
+            assert_eq!(trait_.strength, 0.9);
+        }
+
+        let value: Value = serde_json::from_value(json!({
+            "name": "honesty",
+            "importance": "0.9",
+            "expression": "tells the truth"
+        }))
+        .unwrap();
+        assert_eq!(value.importance, 0.9);
+
+        let mut memory = serde_json::to_value(Memory::default()).unwrap();
+        memory["importance_score"] = json!("0.9");
+        let memory: Memory = serde_json::from_value(memory).unwrap();
+        assert_eq!(memory.importance_score, 0.9);
+        assert_eq!(
+            serde_json::to_value(&memory).unwrap()["importance_score"],
+            json!(0.9)
+        );
+    }
+
+    #[test]
+    fn test_invalid_and_out_of_range_strings() {
+        for strength in [json!("high"), json!("1.5"), json!(1.5), json!(null)] {
+            let result =
+                serde_json::from_value::<Trait>(json!({ "trait": "bold", "strength": strength }));
+            assert!(result.is_err(), "accepted {strength}");
+        }
+
+        let mut memory = serde_json::to_value(Memory::default()).unwrap();
+        memory["importance_score"] = json!("-0.1");
+        assert!(serde_json::from_value::<Memory>(memory).is_err());
+    }
+}
@@ -1,33 +1,32 @@
-mod relationship;
-mod traits;
-mod value;
-mod voice;
+pub mod compatibility;
+pub mod relationship;
+pub mod traits;
+pub mod value;
+pub mod voice;
 
-use relationship::Relationship;
+use compatibility::CompatibilityReport;
+use relationship::{Relationship, RelationshipBuilder, RelationshipBuilderError};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error, fmt};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use traits::Trait;
 use value::Value;
-use voice::Voice;
+use voice::{Voice, VoiceBuilder, VoiceBuilderError};
 
-use crate::Soulgraph;
-
-#[derive(Debug)]
-pub struct CorruptPersonality;
-
-impl fmt::Display for CorruptPersonality {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "personality is corrupted")
-    }
-}
-
-impl error::Error for CorruptPersonality {}
+use crate::{
+    response::{self, DeleteResult},
+    Soulgraph, SoulgraphError,
+};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Personality {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<uuid::Uuid>,
     pub name: String,
+    #[serde(deserialize_with = "traits::deserialize_list")]
     pub traits: Vec<Trait>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub values: Option<Vec<Value>>,
@@ -35,10 +34,27 @@ pub struct Personality {
     pub voice: Option<Voice>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relationship: Option<Relationship>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_sorted_metadata"
+    )]
     pub metadata: Option<HashMap<String, String>>,
 }
 
+/// Emits metadata keys in sorted order so serialized output is stable.
+fn serialize_sorted_metadata<S>(
+    metadata: &Option<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    metadata
+        .as_ref()
+        .map(|map| map.iter().collect::<BTreeMap<_, _>>())
+        .serialize(serializer)
+}
+
 impl Default for Personality {
     fn default() -> Self {
         Self {
@@ -50,12 +66,14 @@ impl Default for Personality {
                     r#trait: "helpful".to_string(),
                     strength: 0.9,
                     expression_rules: Some(vec!["always seeks to assist".to_string()]),
+                    category: None,
                 },
                 Trait {
                     id: None,
                     r#trait: "professional".to_string(),
                     strength: 0.8,
                     expression_rules: Some(vec!["maintains appropriate boundaries".to_string()]),
+                    category: None,
                 },
             ],
             values: None,
@@ -75,6 +93,97 @@ impl Default for Personality {
     }
 }
 
+/// A numeric field clamped into range by [`Personality::from_value_lenient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correction {
+    /// JSON pointer to the repaired field, e.g. `/traits/0/strength`.
+    pub path: String,
+    pub original: f64,
+    pub corrected: f64,
+}
+
+/// Clamps every `field` of the objects in `list` into `0..=1`. The list may be an array or,
+/// as for traits, an object keyed by name; numeric strings are clamped like numbers.
+fn clamp_unit_fields(
+    value: &mut serde_json::Value,
+    list: &str,
+    field: &str,
+    corrections: &mut Vec<Correction>,
+) {
+    let items: Vec<(String, &mut serde_json::Value)> = match value.get_mut(list) {
+        Some(serde_json::Value::Array(items)) => items
+            .iter_mut()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item))
+            .collect(),
+        Some(serde_json::Value::Object(items)) => items
+            .iter_mut()
+            .map(|(name, item)| (name.replace('~', "~0").replace('/', "~1"), item))
+            .collect(),
+        _ => return,
+    };
+    for (key, item) in items {
+        let Some(number) = item.get_mut(field) else {
+            continue;
+        };
+        let original = match &*number {
+            serde_json::Value::String(string) => string.trim().parse::<f64>().ok(),
+            number => number.as_f64(),
+        };
+        let Some(original) = original else {
+            continue;
+        };
+        let corrected = original.clamp(0.0, 1.0);
+        if corrected != original {
+            *number = serde_json::json!(corrected);
+            corrections.push(Correction {
+                path: format!("/{list}/{key}/{field}"),
+                original,
+                corrected,
+            });
+        }
+    }
+}
+
+/// Key used by `Personality::traits_by_category` for traits without a category.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// Appends traits, replacing any existing trait with the same name (case-insensitive) in
+/// place so a personality never holds two traits of one name.
+impl Extend<Trait> for Personality {
+    fn extend<I: IntoIterator<Item = Trait>>(&mut self, iter: I) {
+        for trait_ in iter {
+            match self
+                .traits
+                .iter_mut()
+                .find(|existing| existing.r#trait.eq_ignore_ascii_case(&trait_.r#trait))
+            {
+                Some(existing) => *existing = trait_,
+                None => self.traits.push(trait_),
+            }
+        }
+    }
+}
+
+/// Maps each trait name to its strength; if a name repeats, the last trait wins.
+impl From<&Personality> for HashMap<String, f32> {
+    fn from(personality: &Personality) -> Self {
+        personality
+            .traits
+            .iter()
+            .map(<(String, f32)>::from)
+            .collect()
+    }
+}
+
+impl TryFrom<serde_json::Value> for Personality {
+    type Error = SoulgraphError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
 #[derive(Default)]
 pub struct PersonalityBuilder {
     id: Option<uuid::Uuid>,
@@ -84,12 +193,19 @@ pub struct PersonalityBuilder {
     voice: Option<Voice>,
     relationship: Option<Relationship>,
     metadata: Option<HashMap<String, String>>,
+    strength_budget: Option<f32>,
+    auto_normalize_strengths: bool,
+    nested_error: Option<PersonalityBuilderError>,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum PersonalityBuilderError {
     MissingName,
     NoTraits,
+    DuplicateValue(String),
+    InvalidVoice(VoiceBuilderError),
+    InvalidRelationship(RelationshipBuilderError),
+    StrengthBudgetExceeded { total: f32, budget: f32 },
 }
 
 impl std::fmt::Display for PersonalityBuilderError {
@@ -97,32 +213,85 @@ impl std::fmt::Display for PersonalityBuilderError {
         match self {
             PersonalityBuilderError::MissingName => write!(f, "name is required"),
             PersonalityBuilderError::NoTraits => write!(f, "at least one trait is required"),
+            PersonalityBuilderError::DuplicateValue(name) => {
+                write!(f, "value \"{name}\" is defined more than once")
+            }
+            PersonalityBuilderError::InvalidVoice(reason) => write!(f, "invalid voice: {reason}"),
+            PersonalityBuilderError::InvalidRelationship(err) => {
+                write!(f, "invalid relationship: {err}")
+            }
+            PersonalityBuilderError::StrengthBudgetExceeded { total, budget } => {
+                write!(
+                    f,
+                    "trait strengths sum to {total}, exceeding the budget of {budget}"
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for PersonalityBuilderError {}
 
+/// Errors from editing an existing `Personality`.
+#[derive(Debug, PartialEq)]
+pub enum PersonalityError {
+    TraitNotFound(String),
+    StrengthOutOfRange(f32),
+}
+
+impl std::fmt::Display for PersonalityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersonalityError::TraitNotFound(name) => write!(f, "no trait named \"{name}\""),
+            PersonalityError::StrengthOutOfRange(strength) => {
+                write!(f, "strength {strength} is outside 0..=1")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersonalityError {}
+
 impl PersonalityBuilder {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Pre-populates a builder with every field of `personality`, including its id and metadata.
+    pub fn from_personality(personality: &Personality) -> Self {
+        Self {
+            id: personality.id,
+            name: Some(personality.name.clone()),
+            traits: personality.traits.clone(),
+            values: personality.values.clone(),
+            voice: personality.voice.clone(),
+            relationship: personality.relationship.clone(),
+            metadata: personality.metadata.clone(),
+            strength_budget: None,
+            auto_normalize_strengths: false,
+            nested_error: None,
+        }
+    }
+
+    #[must_use]
     pub fn id(mut self, id: uuid::Uuid) -> Self {
         self.id = Some(id);
         self
     }
 
+    #[must_use]
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
         self
     }
 
+    #[must_use]
     pub fn add_trait(mut self, trait_: Trait) -> Self {
         self.traits.push(trait_);
         self
     }
 
+    #[must_use]
     pub fn add_value(mut self, value: Value) -> Self {
         if self.values.is_none() {
             self.values = Some(Vec::new());
@@ -133,16 +302,103 @@ impl PersonalityBuilder {
         self
     }
 
+    #[must_use]
     pub fn voice(mut self, voice: Voice) -> Self {
         self.voice = Some(voice);
         self
     }
 
+    #[must_use]
     pub fn relationship(mut self, relationship: Relationship) -> Self {
         self.relationship = Some(relationship);
         self
     }
 
+    /// Builds the voice inline; a voice validation error is returned from `build`.
+    #[must_use]
+    pub fn with_voice(mut self, configure: impl FnOnce(VoiceBuilder) -> VoiceBuilder) -> Self {
+        match configure(VoiceBuilder::new()).build() {
+            Ok(voice) => self.voice = Some(voice),
+            Err(err) => {
+                self.nested_error
+                    .get_or_insert(PersonalityBuilderError::InvalidVoice(err));
+            }
+        }
+        self
+    }
+
+    /// Builds the relationship inline; a relationship validation error is returned from `build`.
+    #[must_use]
+    pub fn with_relationship(
+        mut self,
+        configure: impl FnOnce(RelationshipBuilder) -> RelationshipBuilder,
+    ) -> Self {
+        match configure(RelationshipBuilder::new()).build() {
+            Ok(relationship) => self.relationship = Some(relationship),
+            Err(err) => {
+                self.nested_error
+                    .get_or_insert(PersonalityBuilderError::InvalidRelationship(err));
+            }
+        }
+        self
+    }
+
+    /// Unsets the voice, discarding any error from an earlier `with_voice`.
+    #[must_use]
+    pub fn clear_voice(mut self) -> Self {
+        self.voice = None;
+        if matches!(
+            self.nested_error,
+            Some(PersonalityBuilderError::InvalidVoice(_))
+        ) {
+            self.nested_error = None;
+        }
+        self
+    }
+
+    /// Unsets the relationship, discarding any error from an earlier `with_relationship`.
+    #[must_use]
+    pub fn clear_relationship(mut self) -> Self {
+        self.relationship = None;
+        if matches!(
+            self.nested_error,
+            Some(PersonalityBuilderError::InvalidRelationship(_))
+        ) {
+            self.nested_error = None;
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn clear_values(mut self) -> Self {
+        self.values = None;
+        self
+    }
+
+    #[must_use]
+    pub fn clear_metadata(mut self) -> Self {
+        self.metadata = None;
+        self
+    }
+
+    /// Caps the sum of all trait strengths; `build` fails with
+    /// [`PersonalityBuilderError::StrengthBudgetExceeded`] when it is exceeded.
+    #[must_use]
+    pub fn strength_budget(mut self, budget: f32) -> Self {
+        self.strength_budget = Some(budget);
+        self
+    }
+
+    /// When enabled, `build` scales trait strengths so they sum to `1.0`, for callers who
+    /// think in relative weights. Off by default; strengths summing to zero are left as is.
+    /// A [`strength_budget`](Self::strength_budget) applies to the normalized strengths.
+    #[must_use]
+    pub fn auto_normalize_strengths(mut self, enabled: bool) -> Self {
+        self.auto_normalize_strengths = enabled;
+        self
+    }
+
+    #[must_use]
     pub fn set_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         if self.metadata.is_none() {
             self.metadata = Some(HashMap::new());
@@ -153,12 +409,36 @@ impl PersonalityBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Personality, PersonalityBuilderError> {
+    pub fn build(mut self) -> Result<Personality, PersonalityBuilderError> {
+        if let Some(err) = self.nested_error {
+            return Err(err);
+        }
         let name = self.name.ok_or(PersonalityBuilderError::MissingName)?;
 
         if self.traits.is_empty() {
             return Err(PersonalityBuilderError::NoTraits);
         }
+        if self.auto_normalize_strengths {
+            let total: f32 = self.traits.iter().map(|trait_| trait_.strength).sum();
+            if total > 0.0 {
+                for trait_ in &mut self.traits {
+                    trait_.strength /= total;
+                }
+            }
+        }
+        if let Some(budget) = self.strength_budget {
+            let total: f32 = self.traits.iter().map(|trait_| trait_.strength).sum();
+            if total > budget {
+                return Err(PersonalityBuilderError::StrengthBudgetExceeded { total, budget });
+            }
+        }
+
+        let mut value_names = HashSet::new();
+        for value in self.values.iter().flatten() {
+            if !value_names.insert(value.name.to_lowercase()) {
+                return Err(PersonalityBuilderError::DuplicateValue(value.name.clone()));
+            }
+        }
 
         let mut metadata = self.metadata.unwrap_or_default();
         if !metadata.contains_key("creation_date") {
@@ -190,84 +470,331 @@ impl Personality {
         PersonalityBuilder::default()
     }
 
-    /// Get the `Personality` with the given `id`.
-    pub async fn get(id: &str, soul: Soulgraph) -> Result<Personality, CorruptPersonality> {
-        if let Ok(response) = soul.get(format!("/personality/{id}").as_str()).await {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Personality>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptPersonality),
-                },
-                _ => Err(CorruptPersonality),
+    /// Creates a PersonalityBuilder seeded with this personality, for tweaking and rebuilding
+    pub fn edit(&self) -> PersonalityBuilder {
+        PersonalityBuilder::from_personality(self)
+    }
+
+    /// Groups traits by `category`, with uncategorized traits under [`UNCATEGORIZED`].
+    pub fn traits_by_category(&self) -> HashMap<String, Vec<&Trait>> {
+        let mut groups: HashMap<String, Vec<&Trait>> = HashMap::new();
+        for trait_ in &self.traits {
+            let category = trait_.category.as_deref().unwrap_or(UNCATEGORIZED);
+            groups.entry(category.to_string()).or_default().push(trait_);
+        }
+        groups
+    }
+
+    /// Counts traits per bin after splitting `0..=1` into `buckets` equal bins.
+    ///
+    /// A strength of exactly `1.0` falls into the last bin, out-of-range strengths are
+    /// clamped into range and NaN strengths are not counted.
+    pub fn strength_histogram(&self, buckets: usize) -> Vec<usize> {
+        let mut histogram = vec![0; buckets];
+        if buckets == 0 {
+            return histogram;
+        }
+        for trait_ in self
+            .traits
+            .iter()
+            .filter(|trait_| !trait_.strength.is_nan())
+        {
+            let bin = (trait_.strength.clamp(0.0, 1.0) * buckets as f32) as usize;
+            histogram[bin.min(buckets - 1)] += 1;
+        }
+        histogram
+    }
+
+    /// Adds `value`, initializing the value list if it is absent.
+    pub fn add_value(&mut self, value: Value) {
+        self.values.get_or_insert_with(Vec::new).push(value);
+    }
+
+    /// Removes every value named `name`, returning whether any were removed.
+    pub fn remove_value(&mut self, name: &str) -> bool {
+        let Some(values) = self.values.as_mut() else {
+            return false;
+        };
+        let before = values.len();
+        values.retain(|value| value.name != name);
+        values.len() != before
+    }
+
+    /// The trait named `name` (case-insensitive), if any.
+    pub fn trait_by_name(&self, name: &str) -> Option<&Trait> {
+        self.traits
+            .iter()
+            .find(|trait_| trait_.r#trait.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether the personality has a trait named `name` (case-insensitive).
+    pub fn has_trait(&self, name: &str) -> bool {
+        self.trait_by_name(name).is_some()
+    }
+
+    /// Sets the strength of the trait named `name` (case-insensitive).
+    pub fn set_trait_strength(
+        &mut self,
+        name: &str,
+        strength: f32,
+    ) -> Result<(), PersonalityError> {
+        if !(0.0..=1.0).contains(&strength) {
+            return Err(PersonalityError::StrengthOutOfRange(strength));
+        }
+        let trait_ = self
+            .traits
+            .iter_mut()
+            .find(|trait_| trait_.r#trait.eq_ignore_ascii_case(name))
+            .ok_or_else(|| PersonalityError::TraitNotFound(name.to_string()))?;
+        trait_.strength = strength;
+        Ok(())
+    }
+
+    /// The personality's values, or an empty slice when none are set.
+    pub fn values(&self) -> &[Value] {
+        self.values.as_deref().unwrap_or_default()
+    }
+
+    /// Compares this personality with `other` by shared traits, conflicting values and voice
+    /// similarity; see [`CompatibilityReport`].
+    pub fn is_compatible_with(&self, other: &Personality) -> CompatibilityReport {
+        compatibility::compare(self, other)
+    }
+
+    /// A lossy JSON projection for token-constrained transport: drops expression rules,
+    /// metadata and every id, keeping names, strengths and the remaining sections.
+    pub fn to_compact_json(&self) -> serde_json::Value {
+        let mut compact = self.clone();
+        compact.id = None;
+        compact.metadata = None;
+        for trait_ in &mut compact.traits {
+            trait_.id = None;
+            trait_.expression_rules = None;
+        }
+        for value in compact.values.iter_mut().flatten() {
+            value.id = None;
+            for conflict in value.conflicts.iter_mut().flatten() {
+                conflict.id = None;
+            }
+        }
+        if let Some(voice) = &mut compact.voice {
+            voice.id = None;
+        }
+        if let Some(relationship) = &mut compact.relationship {
+            relationship.id = None;
+            for boundary in &mut relationship.boundaries {
+                boundary.id = None;
+            }
+        }
+        serde_json::to_value(compact).expect("Personality serializes to JSON")
+    }
+
+    /// Renders the personality as a system-prompt block for an LLM.
+    ///
+    /// Sections appear in a fixed order (name, traits, values, voice, relationship) and
+    /// absent ones are left out; metadata is never included, so the output is deterministic.
+    pub fn to_prompt(&self) -> String {
+        let mut sections = vec![format!("You are {}.", self.name)];
+        sections.extend(self.prompt_sections());
+        sections.join("\n\n")
+    }
+
+    /// The sections of [`Personality::to_prompt`] after its opening line.
+    pub(crate) fn prompt_sections(&self) -> Vec<String> {
+        let mut sections = Vec::new();
+
+        if !self.traits.is_empty() {
+            let mut lines = vec!["Traits:".to_string()];
+            for trait_ in &self.traits {
+                let mut line = format!("- {} (strength {:.2})", trait_.r#trait, trait_.strength);
+                if let Some(rules) = trait_.expression_rules.as_ref().filter(|r| !r.is_empty()) {
+                    line.push_str(&format!(": {}", rules.join("; ")));
+                }
+                lines.push(line);
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        if !self.values().is_empty() {
+            let mut lines = vec!["Values:".to_string()];
+            for value in self.values() {
+                lines.push(format!(
+                    "- {} (importance {:.2}): {}",
+                    value.name, value.importance, value.expression
+                ));
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        if let Some(voice) = &self.voice {
+            let mut lines = vec![
+                "Voice:".to_string(),
+                format!("- Style: {}", voice.style),
+                format!("- Tone: {}", voice.tone),
+            ];
+            if !voice.qualities.is_empty() {
+                lines.push(format!("- Qualities: {}", voice.qualities.join(", ")));
+            }
+            if !voice.patterns.is_empty() {
+                lines.push(format!("- Patterns: {}", voice.patterns.join(", ")));
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        if let Some(relationship) = &self.relationship {
+            let mut lines = vec![
+                "Relationship:".to_string(),
+                format!("- Style: {}", relationship.style),
+            ];
+            if !relationship.boundaries.is_empty() {
+                lines.push("- Boundaries:".to_string());
+                for boundary in &relationship.boundaries {
+                    lines.push(format!(
+                        "  - {} ({}): {}",
+                        boundary.r#type,
+                        boundary.enforcement.as_str(),
+                        boundary.description
+                    ));
+                }
             }
-        } else {
-            Err(CorruptPersonality)
+            sections.push(lines.join("\n"));
         }
+
+        sections
+    }
+
+    /// Whether a `Personality` is stored under `id`, checked without fetching it.
+    pub async fn exists(id: &str, soul: &Soulgraph) -> Result<bool, SoulgraphError> {
+        let response = soul.head(format!("/personality/{id}")).await?;
+        response::exists(response).await
+    }
+
+    /// Get the `Personality` with the given `id`.
+    pub async fn get(id: &str, soul: &Soulgraph) -> Result<Personality, SoulgraphError> {
+        soul.get_cached(&format!("/personality/{id}")).await
+    }
+
+    /// Get the `Personality` with the given `id`, repairing out-of-range numbers as
+    /// [`Personality::from_value_lenient`] does.
+    pub async fn get_lenient(
+        id: &str,
+        soul: &Soulgraph,
+    ) -> Result<(Personality, Vec<Correction>), SoulgraphError> {
+        let response = soul.get(format!("/personality/{id}")).await?;
+        Personality::from_value_lenient(response::decode(response).await?)
+    }
+
+    /// Converts `value` after clamping trait strengths and value importances into `0..=1`,
+    /// returning the personality together with every correction that was made.
+    pub fn from_value_lenient(
+        mut value: serde_json::Value,
+    ) -> Result<(Personality, Vec<Correction>), SoulgraphError> {
+        let mut corrections = Vec::new();
+        clamp_unit_fields(&mut value, "traits", "strength", &mut corrections);
+        clamp_unit_fields(&mut value, "values", "importance", &mut corrections);
+        Ok((serde_json::from_value(value)?, corrections))
     }
 
     /// Create a `Personality`.
     pub async fn create(
         personality: &Personality,
-        soul: Soulgraph,
-    ) -> Result<Personality, CorruptPersonality> {
-        if let Ok(response) = soul.post("/personality", personality).await {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Personality>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptPersonality),
-                },
-                _ => Err(CorruptPersonality),
-            }
-        } else {
-            Err(CorruptPersonality)
-        }
+        soul: &Soulgraph,
+    ) -> Result<Personality, SoulgraphError> {
+        let response = soul.post("/personality", personality).await?;
+        response::decode(response).await
     }
 
-    /// Update a `Personality` stored under `id`.
+    /// Update a `Personality` stored under `id` with a PATCH: the server merges the fields
+    /// sent into the stored personality, keeping anything it holds that is not sent.
     pub async fn update(
         id: &str,
         personality: &Personality,
-        soul: Soulgraph,
-    ) -> Result<Personality, CorruptPersonality> {
-        if let Ok(response) = soul
-            .put(format!("/personality/{id}").as_str(), personality)
-            .await
-        {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Personality>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptPersonality),
-                },
-                _ => Err(CorruptPersonality),
-            }
-        } else {
-            Err(CorruptPersonality)
-        }
+        soul: &Soulgraph,
+    ) -> Result<Personality, SoulgraphError> {
+        let response = soul
+            .patch(format!("/personality/{id}"), personality)
+            .await?;
+        response::decode(response).await
+    }
+
+    /// Like [`Personality::update`], but first sets `metadata.last_modified` to today's
+    /// date (UTC) on the copy that is sent.
+    pub async fn touch_and_update(
+        id: &str,
+        personality: &Personality,
+        soul: &Soulgraph,
+    ) -> Result<Personality, SoulgraphError> {
+        let mut touched = personality.clone();
+        touched.metadata.get_or_insert_with(HashMap::new).insert(
+            "last_modified".to_string(),
+            chrono::Utc::now().date_naive().to_string(),
+        );
+        Personality::update(id, &touched, soul).await
+    }
+
+    /// Replace the `Personality` stored under `id` with a PUT: the stored personality
+    /// becomes exactly `personality`, so fields it leaves unset are dropped server-side.
+    /// Use [`Personality::update`] to change only some fields.
+    pub async fn replace(
+        id: &str,
+        personality: &Personality,
+        soul: &Soulgraph,
+    ) -> Result<Personality, SoulgraphError> {
+        let response = soul.put(format!("/personality/{id}"), personality).await?;
+        response::decode(response).await
     }
 
     /// Delete a `Personality` stored under `id`.
-    pub async fn delete(id: &str, soul: Soulgraph) -> Result<(), CorruptPersonality> {
-        if let Ok(response) = soul.delete(format!("/personality/{id}").as_str()).await {
-            match response.status() {
-                reqwest::StatusCode::OK => Ok(()),
-                _ => Err(CorruptPersonality),
-            }
-        } else {
-            Err(CorruptPersonality)
-        }
+    pub async fn delete(id: &str, soul: &Soulgraph) -> Result<DeleteResult, SoulgraphError> {
+        let response = soul.delete(format!("/personality/{id}")).await?;
+        response::delete_result(response, id).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use traits::TraitBuilder;
 
+    #[test]
+    fn test_metadata_serializes_in_sorted_order() {
+        let keys = ["zeta", "alpha", "mu", "beta", "omega", "gamma"];
+        let metadata = |keys: Vec<&str>| {
+            Some(
+                keys.into_iter()
+                    .map(|k| (k.to_string(), "x".to_string()))
+                    .collect(),
+            )
+        };
+        let first = Personality {
+            metadata: metadata(keys.to_vec()),
+            ..Default::default()
+        };
+        let second = Personality {
+            metadata: metadata(keys.iter().rev().copied().collect()),
+            ..Default::default()
+        };
+
+        let first_json = serde_json::to_string(&first).unwrap();
+        let second_json = serde_json::to_string(&second).unwrap();
+        assert_eq!(first_json, second_json);
+
+        let positions: Vec<usize> = ["alpha", "beta", "gamma", "mu", "omega", "zeta"]
+            .iter()
+            .map(|k| first_json.find(&format!("\"{k}\"")).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let reparsed: Personality = serde_json::from_str(&first_json).unwrap();
+        assert_eq!(reparsed.metadata, first.metadata);
+    }
+
     #[test]
     fn test_personality_builder_validation() {
         // Test missing name
         let result = PersonalityBuilder::new()
-            .add_trait(TraitBuilder::new("test").build())
+            .add_trait(TraitBuilder::new("test").build().unwrap())
             .build();
         assert!(matches!(result, Err(PersonalityBuilderError::MissingName)));
 
@@ -276,12 +803,133 @@ mod tests {
         assert!(matches!(result, Err(PersonalityBuilderError::NoTraits)));
     }
 
+    #[test]
+    fn test_personality_builder_rejects_duplicate_values() {
+        let honesty = || {
+            value::ValueBuilder::new()
+                .name("honesty")
+                .importance(0.9)
+                .expression("always tells the truth")
+                .build()
+                .unwrap()
+        };
+
+        let result = PersonalityBuilder::new()
+            .name("Test")
+            .add_trait(TraitBuilder::new("test").build().unwrap())
+            .add_value(honesty())
+            .add_value(honesty())
+            .build();
+
+        assert_eq!(
+            result.unwrap_err(),
+            PersonalityBuilderError::DuplicateValue("honesty".to_string())
+        );
+    }
+
+    #[test]
+    fn test_personality_builder_strength_budget() {
+        let builder = || {
+            PersonalityBuilder::new()
+                .name("Test")
+                .add_trait(TraitBuilder::new("bold").strength(0.75).build().unwrap())
+                .add_trait(TraitBuilder::new("calm").strength(0.5).build().unwrap())
+        };
+
+        let personality = builder().strength_budget(1.25).build().unwrap();
+        assert_eq!(personality.traits.len(), 2);
+
+        let err = builder().strength_budget(1.0).build().unwrap_err();
+        assert_eq!(
+            err,
+            PersonalityBuilderError::StrengthBudgetExceeded {
+                total: 1.25,
+                budget: 1.0
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "trait strengths sum to 1.25, exceeding the budget of 1"
+        );
+    }
+
+    #[test]
+    fn test_personality_builder_auto_normalize_strengths() {
+        let builder = || {
+            PersonalityBuilder::new()
+                .name("Test")
+                .add_trait(TraitBuilder::new("bold").strength(0.9).build().unwrap())
+                .add_trait(TraitBuilder::new("calm").strength(0.6).build().unwrap())
+                .add_trait(TraitBuilder::new("kind").strength(0.3).build().unwrap())
+        };
+
+        let absolute = builder().build().unwrap();
+        let strengths: Vec<f32> = absolute.traits.iter().map(|t| t.strength).collect();
+        assert_eq!(strengths, vec![0.9, 0.6, 0.3]);
+
+        let normalized = builder().auto_normalize_strengths(true).build().unwrap();
+        let total: f32 = normalized.traits.iter().map(|t| t.strength).sum();
+        approx::assert_relative_eq!(total, 1.0);
+        approx::assert_relative_eq!(normalized.traits[0].strength, 0.5);
+        approx::assert_relative_eq!(normalized.traits[2].strength, 0.3 / 1.8);
+    }
+
+    #[test]
+    fn test_personality_builder_inline_voice_and_relationship() {
+        let personality = Personality::builder()
+            .name("Test")
+            .add_trait(TraitBuilder::new("warm").build().unwrap())
+            .with_voice(|voice| {
+                voice
+                    .style("casual")
+                    .tone("warm")
+                    .qualities(vec!["friendly".to_string()])
+                    .patterns(vec!["uses metaphors".to_string()])
+            })
+            .with_relationship(|relationship| {
+                relationship
+                    .style("peer")
+                    .add_boundary(relationship::Boundary::default())
+            })
+            .build()
+            .unwrap();
+
+        let voice = personality.voice.unwrap();
+        assert_eq!(voice.style, "casual");
+        assert_eq!(voice.tone, "warm");
+        assert_eq!(personality.relationship.unwrap().style, "peer");
+    }
+
+    #[test]
+    fn test_personality_builder_inline_errors_surface() {
+        let result = Personality::builder()
+            .name("Test")
+            .add_trait(TraitBuilder::new("warm").build().unwrap())
+            .with_voice(|voice| voice.style("casual").tone("warm"))
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            PersonalityBuilderError::InvalidVoice(VoiceBuilderError::MissingQualities)
+        );
+
+        let result = Personality::builder()
+            .name("Test")
+            .add_trait(TraitBuilder::new("warm").build().unwrap())
+            .with_relationship(|relationship| relationship)
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            PersonalityBuilderError::InvalidRelationship(RelationshipBuilderError::MissingStyle)
+        );
+    }
+
     #[test]
     fn test_personality_builder_minimal() {
         let trait_ = TraitBuilder::new("kind")
             .strength(0.7)
             .add_expression_rule("helps others")
-            .build();
+            .build()
+            .unwrap();
 
         let personality = Personality::builder()
             .name("Test Personality")
@@ -311,13 +959,15 @@ mod tests {
             .strength(0.95)
             .add_expression_rule("use irony to highlight obvious mistakes")
             .add_expression_rule("employ mock praise for poor decisions")
-            .build();
+            .build()
+            .unwrap();
 
         let trait2 = TraitBuilder::new("memetic")
             .strength(0.9)
             .add_expression_rule("reference popular trading memes")
             .add_expression_rule("create memorable catchphrases")
-            .build();
+            .build()
+            .unwrap();
 
         let personality = Personality::builder()
             .id(id)
@@ -342,6 +992,572 @@ mod tests {
         assert_eq!(metadata.get("last_modified").unwrap(), date_string.as_str());
     }
 
+    #[test]
+    fn test_personality_try_from_value() {
+        let personality = Personality::try_from(json!({
+            "name": "Dr. Luna",
+            "traits": [{ "trait": "sarcastic", "strength": 0.95 }]
+        }))
+        .unwrap();
+        assert_eq!(personality.name, "Dr. Luna");
+        assert_eq!(personality.traits[0].r#trait, "sarcastic");
+
+        let result = Personality::try_from(json!({ "name": "Dr. Luna" }));
+        assert!(matches!(result, Err(SoulgraphError::Decode(_))));
+    }
+
+    #[test]
+    fn test_traits_map_form() {
+        let personality: Personality = serde_json::from_str(
+            r#"{
+                "name": "Dr. Luna",
+                "traits": {
+                    "sarcastic": { "strength": 0.9, "expression_rules": ["dry wit"] },
+                    "curious": { "strength": 0.4, "category": "cognitive" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            personality.traits,
+            vec![
+                Trait {
+                    id: None,
+                    r#trait: "sarcastic".to_string(),
+                    strength: 0.9,
+                    expression_rules: Some(vec!["dry wit".to_string()]),
+                    category: None,
+                },
+                Trait {
+                    id: None,
+                    r#trait: "curious".to_string(),
+                    strength: 0.4,
+                    expression_rules: None,
+                    category: Some("cognitive".to_string()),
+                },
+            ]
+        );
+        let value = serde_json::to_value(&personality).unwrap();
+        assert_eq!(value["traits"][0]["trait"], "sarcastic");
+
+        let result = Personality::try_from(json!({ "name": "x", "traits": { "bold": 0.5 } }));
+        assert!(matches!(result, Err(SoulgraphError::Decode(_))));
+    }
+
+    #[test]
+    fn test_personality_edit_preserves_fields() {
+        let id = uuid::Uuid::new_v4();
+        let original = Personality::builder()
+            .id(id)
+            .name("Dr. Luna")
+            .add_trait(
+                TraitBuilder::new("sarcastic")
+                    .strength(0.95)
+                    .build()
+                    .unwrap(),
+            )
+            .voice(voice::Voice::default())
+            .set_metadata("creation_date", "2025-01-11")
+            .set_metadata("author", "luna")
+            .build()
+            .unwrap();
+
+        let edited = original.edit().name("Dr. Sol").build().unwrap();
+
+        assert_eq!(edited.name, "Dr. Sol");
+        assert_eq!(edited.id, Some(id));
+        assert_eq!(edited.traits, original.traits);
+        assert_eq!(edited.voice, original.voice);
+        let metadata = edited.metadata.unwrap();
+        assert_eq!(metadata.get("creation_date").unwrap(), "2025-01-11");
+        assert_eq!(metadata.get("author").unwrap(), "luna");
+    }
+
+    #[test]
+    fn test_traits_by_category() {
+        let personality = Personality::builder()
+            .name("Test")
+            .add_trait(
+                TraitBuilder::new("curious")
+                    .category("cognitive")
+                    .build()
+                    .unwrap(),
+            )
+            .add_trait(
+                TraitBuilder::new("analytical")
+                    .category("cognitive")
+                    .build()
+                    .unwrap(),
+            )
+            .add_trait(
+                TraitBuilder::new("warm")
+                    .category("social")
+                    .build()
+                    .unwrap(),
+            )
+            .add_trait(TraitBuilder::new("odd").build().unwrap())
+            .build()
+            .unwrap();
+
+        let groups = personality.traits_by_category();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["cognitive"].len(), 2);
+        assert_eq!(groups["social"][0].r#trait, "warm");
+        assert_eq!(groups[UNCATEGORIZED][0].r#trait, "odd");
+    }
+
+    #[test]
+    fn test_personality_value_mutation() {
+        let mut personality = Personality::default();
+        assert!(personality.values.is_none());
+        assert!(personality.values().is_empty());
+        assert!(!personality.remove_value("honesty"));
+
+        personality.add_value(
+            value::ValueBuilder::new()
+                .name("honesty")
+                .importance(0.9)
+                .expression("always tells the truth")
+                .build()
+                .unwrap(),
+        );
+        personality.add_value(
+            value::ValueBuilder::new()
+                .name("courage")
+                .importance(0.7)
+                .expression("faces challenges")
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(personality.values().len(), 2);
+
+        assert!(personality.remove_value("honesty"));
+        assert!(!personality.remove_value("honesty"));
+        assert_eq!(personality.values().len(), 1);
+        assert_eq!(personality.values()[0].name, "courage");
+    }
+
+    #[test]
+    fn test_from_value_lenient_clamps_out_of_range() {
+        let value = json!({
+            "name": "Test",
+            "traits": [
+                { "trait": "warm", "strength": 0.5 },
+                { "trait": "bold", "strength": 1.5 }
+            ],
+            "values": [
+                { "name": "honesty", "importance": -0.2, "expression": "tells the truth" }
+            ]
+        });
+
+        let (personality, corrections) = Personality::from_value_lenient(value).unwrap();
+        assert_eq!(personality.traits[0].strength, 0.5);
+        assert_eq!(personality.traits[1].strength, 1.0);
+        assert_eq!(personality.values()[0].importance, 0.0);
+        assert_eq!(
+            corrections,
+            vec![
+                Correction {
+                    path: "/traits/1/strength".to_string(),
+                    original: 1.5,
+                    corrected: 1.0,
+                },
+                Correction {
+                    path: "/values/0/importance".to_string(),
+                    original: -0.2,
+                    corrected: 0.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_value_lenient_clamps_string_numbers_and_trait_maps() {
+        let value = json!({
+            "name": "Test",
+            "traits": {
+                "bold": { "strength": "1.5" },
+                "warm": { "strength": "0.5" }
+            }
+        });
+
+        let (personality, corrections) = Personality::from_value_lenient(value).unwrap();
+        let bold = personality.trait_by_name("bold").unwrap();
+        assert_eq!(bold.strength, 1.0);
+        assert_eq!(personality.trait_by_name("warm").unwrap().strength, 0.5);
+        assert_eq!(
+            corrections,
+            vec![Correction {
+                path: "/traits/bold/strength".to_string(),
+                original: 1.5,
+                corrected: 1.0,
+            }]
+        );
+
+        let value = json!({
+            "name": "Test",
+            "traits": [{ "trait": "bold", "strength": "-3" }]
+        });
+        let (personality, corrections) = Personality::from_value_lenient(value).unwrap();
+        assert_eq!(personality.traits[0].strength, 0.0);
+        assert_eq!(corrections[0].path, "/traits/0/strength");
+        assert_eq!(corrections[0].original, -3.0);
+    }
+
+    #[tokio::test]
+    async fn test_personality_exists() {
+        use wiremock::{
+            matchers::{method, path},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/personality/abc"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/personality/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        assert!(Personality::exists("abc", &soulgraph).await.unwrap());
+        assert!(!Personality::exists("missing", &soulgraph).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_replace_puts_and_update_patches() {
+        use wiremock::{
+            matchers::{body_json, method, path},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let personality = Personality::default();
+        let server = MockServer::start().await;
+        for verb in ["PUT", "PATCH"] {
+            Mock::given(method(verb))
+                .and(path("/personality/abc"))
+                .and(body_json(&personality))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&personality))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let replaced = Personality::replace("abc", &personality, &soulgraph)
+            .await
+            .unwrap();
+        let updated = Personality::update("abc", &personality, &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(replaced, personality);
+        assert_eq!(updated, personality);
+
+        let methods: Vec<String> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.method.to_string())
+            .collect();
+        assert_eq!(methods, vec!["PUT", "PATCH"]);
+    }
+
+    #[tokio::test]
+    async fn test_touch_and_update_bumps_last_modified() {
+        use wiremock::{
+            matchers::{method, path},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let personality = Personality {
+            metadata: Some(HashMap::from([
+                ("last_modified".to_string(), "2000-01-01".to_string()),
+                ("author".to_string(), "tests".to_string()),
+            ])),
+            ..Personality::default()
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/personality/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&personality))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        Personality::touch_and_update("abc", &personality, &soulgraph)
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let today = chrono::Utc::now().date_naive().to_string();
+        assert_eq!(body["metadata"]["last_modified"], today);
+        assert_eq!(body["metadata"]["author"], "tests");
+        // The caller's copy is left alone.
+        assert_eq!(personality.metadata.unwrap()["last_modified"], "2000-01-01");
+    }
+
+    #[tokio::test]
+    async fn test_get_lenient() {
+        use wiremock::{
+            matchers::{method, path},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "name": "Test",
+                "traits": [{ "trait": "bold", "strength": 1.5 }]
+            })))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let (personality, corrections) = Personality::get_lenient("abc", &soulgraph).await.unwrap();
+        assert_eq!(personality.traits[0].strength, 1.0);
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].path, "/traits/0/strength");
+    }
+
+    #[test]
+    fn test_strength_map_from_default_personality() {
+        let strengths = HashMap::<String, f32>::from(&Personality::default());
+        assert_eq!(
+            strengths,
+            HashMap::from([
+                ("helpful".to_string(), 0.9),
+                ("professional".to_string(), 0.8),
+            ])
+        );
+
+        let pair: (String, f32) = (&Personality::default().traits[0]).into();
+        assert_eq!(pair, ("helpful".to_string(), 0.9));
+    }
+
+    #[test]
+    fn test_to_compact_json() {
+        let personality = Personality::builder()
+            .id(uuid::Uuid::new_v4())
+            .name("Scout")
+            .add_trait(
+                TraitBuilder::new("curious")
+                    .id(uuid::Uuid::new_v4())
+                    .strength(0.7)
+                    .add_expression_rule("asks follow-up questions")
+                    .build()
+                    .unwrap(),
+            )
+            .relationship(Relationship {
+                id: Some(uuid::Uuid::new_v4()),
+                ..Relationship::default()
+            })
+            .set_metadata("source", "test")
+            .build()
+            .unwrap();
+
+        let compact = personality.to_compact_json();
+        assert!(compact.get("id").is_none());
+        assert!(compact.get("metadata").is_none());
+        assert_eq!(compact["name"], personality.name);
+        let curious = &compact["traits"][0];
+        assert_eq!(
+            curious,
+            &serde_json::json!({ "trait": "curious", "strength": 0.7 })
+        );
+        assert!(compact["relationship"].get("id").is_none());
+        assert_eq!(
+            compact["relationship"]["style"],
+            "mentor-like guidance through respect and shared purpose"
+        );
+        assert!(!compact.to_string().contains("expression_rules"));
+    }
+
+    #[test]
+    fn test_to_prompt_default_personality() {
+        assert_eq!(
+            Personality::default().to_prompt(),
+            "You are AI Assistant.\n\
+             \n\
+             Traits:\n\
+             - helpful (strength 0.90): always seeks to assist\n\
+             - professional (strength 0.80): maintains appropriate boundaries"
+        );
+    }
+
+    #[test]
+    fn test_to_prompt_full_personality() {
+        let personality = Personality {
+            values: Some(vec![Value {
+                name: "honesty".to_string(),
+                importance: 0.95,
+                expression: "tells the truth".to_string(),
+                ..Value::default()
+            }]),
+            voice: Some(Voice::default()),
+            relationship: Some(Relationship::default()),
+            ..Personality::default()
+        };
+
+        let prompt = personality.to_prompt();
+        let sections: Vec<&str> = prompt.split("\n\n").collect();
+        assert_eq!(sections.len(), 5);
+        assert!(sections[1].starts_with("Traits:\n"));
+        assert_eq!(
+            sections[2],
+            "Values:\n- honesty (importance 0.95): tells the truth"
+        );
+        assert!(sections[3].starts_with("Voice:\n- Style: sternly encouraging\n- Tone: "));
+        assert!(sections[3].contains("- Qualities: commanding, confident"));
+        assert!(sections[4].ends_with(
+            "- Boundaries:\n  - core values (flexible): \
+             becomes more formal and distant if questioned on core values"
+        ));
+    }
+
+    #[test]
+    fn test_extend_traits_overwrites_duplicates() {
+        let mut personality = Personality::default();
+        personality.extend(vec![
+            TraitBuilder::new("curious").strength(0.6).build().unwrap(),
+            TraitBuilder::new("Helpful").strength(0.4).build().unwrap(),
+            TraitBuilder::new("curious").strength(0.7).build().unwrap(),
+        ]);
+
+        let traits: Vec<(&str, f32)> = personality
+            .traits
+            .iter()
+            .map(|trait_| (trait_.r#trait.as_str(), trait_.strength))
+            .collect();
+        assert_eq!(
+            traits,
+            vec![("Helpful", 0.4), ("professional", 0.8), ("curious", 0.7)]
+        );
+        assert!(personality.traits[0].expression_rules.is_none());
+    }
+
+    #[test]
+    fn test_strength_histogram() {
+        let personality = Personality {
+            traits: [0.0, 0.1, 0.24, 0.25, 0.6, 0.99, 1.0, f32::NAN]
+                .into_iter()
+                .enumerate()
+                .map(|(index, strength)| {
+                    TraitBuilder::new(&format!("trait-{index}"))
+                        .strength(strength)
+                        .build()
+                        .unwrap()
+                })
+                .collect(),
+            ..Personality::default()
+        };
+
+        assert_eq!(personality.strength_histogram(4), vec![3, 1, 1, 2]);
+        assert_eq!(personality.strength_histogram(1), vec![7]);
+        assert!(personality.strength_histogram(0).is_empty());
+    }
+
+    #[test]
+    fn test_strength_histogram_last_bin_boundary() {
+        let personality = Personality {
+            traits: vec![TraitBuilder::new("max").strength(1.0).build().unwrap()],
+            ..Personality::default()
+        };
+        assert_eq!(personality.strength_histogram(10)[9], 1);
+        assert_eq!(personality.strength_histogram(10).iter().sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn test_builder_clear_optional_fields() {
+        let template = PersonalityBuilder::new()
+            .name("Template")
+            .add_trait(TraitBuilder::new("warm").build().unwrap())
+            .voice(Voice::default())
+            .relationship(Relationship::default())
+            .add_value(Value {
+                name: "honesty".to_string(),
+                ..Value::default()
+            });
+
+        let personality = template
+            .clear_voice()
+            .clear_relationship()
+            .clear_values()
+            .build()
+            .unwrap();
+        assert_eq!(personality.voice, None);
+        assert_eq!(personality.relationship, None);
+        assert_eq!(personality.values, None);
+
+        let personality = PersonalityBuilder::new()
+            .name("Recovered")
+            .add_trait(TraitBuilder::new("warm").build().unwrap())
+            .with_voice(|voice| voice)
+            .clear_voice()
+            .build()
+            .unwrap();
+        assert_eq!(personality.voice, None);
+    }
+
+    #[test]
+    fn test_trait_by_name() {
+        let personality = PersonalityBuilder::new()
+            .name("Test")
+            .add_trait(
+                TraitBuilder::new("sarcastic")
+                    .strength(0.9)
+                    .build()
+                    .unwrap(),
+            )
+            .add_trait(TraitBuilder::new("Curious").strength(0.4).build().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            personality.trait_by_name("sarcastic").unwrap().strength,
+            0.9
+        );
+        assert_eq!(
+            personality.trait_by_name("CURIOUS").unwrap().r#trait,
+            "Curious"
+        );
+        assert!(personality.has_trait("curious"));
+
+        assert!(personality.trait_by_name("stoic").is_none());
+        assert!(!personality.has_trait("stoic"));
+    }
+
+    #[test]
+    fn test_set_trait_strength() {
+        let mut personality = Personality::default();
+        let name = personality.traits[0].r#trait.to_uppercase();
+
+        personality.set_trait_strength(&name, 0.25).unwrap();
+        assert_eq!(personality.traits[0].strength, 0.25);
+
+        assert_eq!(
+            personality.set_trait_strength("nonexistent", 0.5),
+            Err(PersonalityError::TraitNotFound("nonexistent".to_string()))
+        );
+        assert_eq!(
+            personality.set_trait_strength(&name, 1.5),
+            Err(PersonalityError::StrengthOutOfRange(1.5))
+        );
+        assert!(matches!(
+            personality.set_trait_strength(&name, f32::NAN),
+            Err(PersonalityError::StrengthOutOfRange(_))
+        ));
+        assert_eq!(personality.traits[0].strength, 0.25);
+    }
+
     #[test]
     fn test_default_personality() {
         let personality = Personality::default();
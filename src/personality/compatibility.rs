@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use super::Personality;
+
+const TRAITS_WEIGHT: f32 = 0.4;
+const VALUES_WEIGHT: f32 = 0.35;
+const VOICE_WEIGHT: f32 = 0.25;
+/// Voice score used when either personality has no voice.
+const NEUTRAL_VOICE: f32 = 0.5;
+
+/// How well two personalities fit together, from [`Personality::is_compatible_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompatibilityReport {
+    /// Overall compatibility, from `0.0` (incompatible) to `1.0`.
+    pub score: f32,
+    /// Trait names both personalities have, lowercased and sorted.
+    pub shared_traits: Vec<String>,
+    /// Values of either personality named in the other's value conflicts, lowercased and sorted.
+    pub conflicting_values: Vec<String>,
+    /// [`Voice::similarity`](super::voice::Voice::similarity) of the two voices, or `None`
+    /// when either is missing.
+    pub voice_similarity: Option<f32>,
+}
+
+/// Scores `a` against `b`.
+///
+/// The score weights the Jaccard overlap of trait names at 0.4, the share of values not in
+/// conflict at 0.35 and voice similarity at 0.25 (0.5 when a voice is missing).
+pub(super) fn compare(a: &Personality, b: &Personality) -> CompatibilityReport {
+    let traits_a = trait_names(a);
+    let traits_b = trait_names(b);
+    let mut shared_traits: Vec<String> = traits_a.intersection(&traits_b).cloned().collect();
+    shared_traits.sort();
+    let trait_union = traits_a.union(&traits_b).count();
+    let trait_overlap = if trait_union == 0 {
+        0.0
+    } else {
+        shared_traits.len() as f32 / trait_union as f32
+    };
+
+    let values_a = value_names(a);
+    let values_b = value_names(b);
+    let mut conflicting_values: Vec<String> = values_a
+        .intersection(&conflict_names(b))
+        .chain(values_b.intersection(&conflict_names(a)))
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    conflicting_values.sort();
+    let value_union = values_a.union(&values_b).count();
+    let value_harmony = if value_union == 0 {
+        1.0
+    } else {
+        1.0 - conflicting_values.len() as f32 / value_union as f32
+    };
+
+    let voice_similarity = match (&a.voice, &b.voice) {
+        (Some(voice_a), Some(voice_b)) => Some(voice_a.similarity(voice_b)),
+        _ => None,
+    };
+
+    let score = TRAITS_WEIGHT * trait_overlap
+        + VALUES_WEIGHT * value_harmony
+        + VOICE_WEIGHT * voice_similarity.unwrap_or(NEUTRAL_VOICE);
+
+    CompatibilityReport {
+        score: score.clamp(0.0, 1.0),
+        shared_traits,
+        conflicting_values,
+        voice_similarity,
+    }
+}
+
+fn trait_names(personality: &Personality) -> HashSet<String> {
+    personality
+        .traits
+        .iter()
+        .map(|trait_| normalize(&trait_.r#trait))
+        .collect()
+}
+
+fn value_names(personality: &Personality) -> HashSet<String> {
+    personality
+        .values()
+        .iter()
+        .map(|value| normalize(&value.name))
+        .collect()
+}
+
+fn conflict_names(personality: &Personality) -> HashSet<String> {
+    personality
+        .values()
+        .iter()
+        .flat_map(|value| value.conflicts.iter().flatten())
+        .map(|conflict| normalize(&conflict.value))
+        .collect()
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::personality::{
+        traits::TraitBuilder,
+        value::{Value, ValueConflict},
+        voice::Voice,
+    };
+
+    fn value(name: &str, conflicts: &[&str]) -> Value {
+        Value {
+            name: name.to_string(),
+            expression: format!("acts on {name}"),
+            conflicts: (!conflicts.is_empty()).then(|| {
+                conflicts
+                    .iter()
+                    .map(|conflict| ValueConflict {
+                        id: None,
+                        value: conflict.to_string(),
+                        resolution: "talk it through".to_string(),
+                    })
+                    .collect()
+            }),
+            ..Value::default()
+        }
+    }
+
+    fn personality(traits: &[&str], values: Vec<Value>, tone: &str) -> Personality {
+        Personality {
+            traits: traits
+                .iter()
+                .map(|name| TraitBuilder::new(name).build().unwrap())
+                .collect(),
+            values: Some(values),
+            voice: Some(Voice {
+                tone: tone.to_string(),
+                ..Voice::default()
+            }),
+            ..Personality::default()
+        }
+    }
+
+    #[test]
+    fn test_similar_personalities_are_compatible() {
+        let a = personality(&["curious", "Kind"], vec![value("honesty", &[])], "warm");
+        let b = personality(
+            &["kind", "curious", "patient"],
+            vec![value("honesty", &[]), value("loyalty", &[])],
+            "warm",
+        );
+
+        let report = a.is_compatible_with(&b);
+        assert_eq!(report.shared_traits, vec!["curious", "kind"]);
+        assert!(report.conflicting_values.is_empty());
+        assert_eq!(report.voice_similarity, Some(1.0));
+        approx::assert_relative_eq!(report.score, 0.4 * (2.0 / 3.0) + 0.35 + 0.25);
+    }
+
+    #[test]
+    fn test_conflicting_personalities_score_low() {
+        let a = personality(&["bold"], vec![value("ambition", &["humility"])], "brash");
+        let mut b = personality(&["meek"], vec![value("humility", &[])], "gentle");
+        b.voice = Some(Voice {
+            id: None,
+            style: "quiet".to_string(),
+            tone: "gentle".to_string(),
+            qualities: vec!["soft-spoken".to_string()],
+            patterns: vec!["asks questions".to_string()],
+        });
+
+        let report = a.is_compatible_with(&b);
+        assert!(report.shared_traits.is_empty());
+        assert_eq!(report.conflicting_values, vec!["humility"]);
+        assert_eq!(report.voice_similarity, Some(0.0));
+        approx::assert_relative_eq!(report.score, 0.35 * 0.5);
+        assert_eq!(report.score, b.is_compatible_with(&a).score);
+    }
+}
@@ -2,7 +2,11 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Relationship {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<uuid::Uuid>,
     pub style: String,
     pub boundaries: Vec<Boundary>,
@@ -18,6 +22,83 @@ impl Default for Relationship {
     }
 }
 
+impl Relationship {
+    pub fn builder() -> RelationshipBuilder {
+        RelationshipBuilder::default()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RelationshipBuilderError {
+    MissingStyle,
+}
+
+impl std::fmt::Display for RelationshipBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelationshipBuilderError::MissingStyle => write!(f, "style is required"),
+        }
+    }
+}
+
+impl std::error::Error for RelationshipBuilderError {}
+
+#[derive(Default)]
+pub struct RelationshipBuilder {
+    style: Option<String>,
+    boundaries: Vec<Boundary>,
+    default_enforcement: EnforcementType,
+}
+
+impl RelationshipBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn style(mut self, style: impl Into<String>) -> Self {
+        self.style = Some(style.into());
+        self
+    }
+
+    #[must_use]
+    pub fn add_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundaries.push(boundary);
+        self
+    }
+
+    /// Enforcement given to boundaries added afterwards with `add_simple_boundary`.
+    #[must_use]
+    pub fn default_enforcement(mut self, enforcement: EnforcementType) -> Self {
+        self.default_enforcement = enforcement;
+        self
+    }
+
+    /// Adds a boundary with the builder's default enforcement (see `default_enforcement`).
+    #[must_use]
+    pub fn add_simple_boundary(
+        mut self,
+        r#type: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.boundaries.push(Boundary {
+            id: None,
+            r#type: r#type.into(),
+            description: description.into(),
+            enforcement: self.default_enforcement.clone(),
+        });
+        self
+    }
+
+    pub fn build(self) -> Result<Relationship, RelationshipBuilderError> {
+        Ok(Relationship {
+            id: None,
+            style: self.style.ok_or(RelationshipBuilderError::MissingStyle)?,
+            boundaries: self.boundaries,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum EnforcementType {
@@ -27,9 +108,23 @@ pub enum EnforcementType {
     Situational,
 }
 
+impl EnforcementType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnforcementType::Strict => "strict",
+            EnforcementType::Flexible => "flexible",
+            EnforcementType::Situational => "situational",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Boundary {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<uuid::Uuid>,
     pub r#type: String,
     pub description: String,
@@ -52,6 +147,44 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_simple_boundaries_inherit_default_enforcement() {
+        let relationship = RelationshipBuilder::new()
+            .style("mentor")
+            .add_simple_boundary("personal", "keeps private life private")
+            .default_enforcement(EnforcementType::Strict)
+            .add_simple_boundary("ethics", "refuses harmful requests")
+            .add_boundary(Boundary {
+                id: None,
+                r#type: "tone".to_string(),
+                description: "stays polite".to_string(),
+                enforcement: EnforcementType::Situational,
+            })
+            .add_simple_boundary("safety", "never shares credentials")
+            .build()
+            .unwrap();
+
+        let enforcements: Vec<&EnforcementType> = relationship
+            .boundaries
+            .iter()
+            .map(|boundary| &boundary.enforcement)
+            .collect();
+        assert_eq!(
+            enforcements,
+            vec![
+                &EnforcementType::Flexible,
+                &EnforcementType::Strict,
+                &EnforcementType::Situational,
+                &EnforcementType::Strict,
+            ]
+        );
+        assert_eq!(relationship.boundaries[1].r#type, "ethics");
+        assert_eq!(
+            relationship.boundaries[1].description,
+            "refuses harmful requests"
+        );
+    }
+
     #[test]
     fn test_relationship_serialization() {
         let relationship = Relationship {
@@ -105,6 +238,24 @@ mod tests {
         assert_eq!(boundary.enforcement, EnforcementType::Flexible);
     }
 
+    #[test]
+    fn test_relationship_builder() {
+        let relationship = RelationshipBuilder::new()
+            .style("professional")
+            .add_boundary(Boundary::default())
+            .build()
+            .unwrap();
+
+        assert!(relationship.id.is_none());
+        assert_eq!(relationship.style, "professional");
+        assert_eq!(relationship.boundaries, vec![Boundary::default()]);
+
+        assert_eq!(
+            RelationshipBuilder::new().build().unwrap_err(),
+            RelationshipBuilderError::MissingStyle
+        );
+    }
+
     #[test]
     fn test_boundary_default() {
         let boundary = Boundary::default();
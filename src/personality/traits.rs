@@ -1,37 +1,175 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{cmp::Ordering, fmt};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Trait {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
+    #[serde(alias = "name")]
     pub r#trait: String,
+    #[serde(
+        serialize_with = "crate::precision::serialize",
+        deserialize_with = "crate::number::deserialize_unit"
+    )]
     pub strength: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expression_rules: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+/// Deserializes a trait list given either as an array or as an object keyed by trait name,
+/// e.g. `{"sarcastic": {"strength": 0.9}}`. Map entries keep their document order only when
+/// decoding directly from text; from a `serde_json::Value` they come back sorted by name.
+pub(crate) fn deserialize_list<'de, D>(deserializer: D) -> Result<Vec<Trait>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct TraitsVisitor;
+
+    impl<'de> Visitor<'de> for TraitsVisitor {
+        type Value = Vec<Trait>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an array of traits or a map keyed by trait name")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut traits = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(trait_) = seq.next_element()? {
+                traits.push(trait_);
+            }
+            Ok(traits)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut traits = Vec::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some((name, mut body)) = map.next_entry::<String, serde_json::Value>()? {
+                let Some(fields) = body.as_object_mut() else {
+                    return Err(de::Error::custom(format!(
+                        "trait `{name}` must be an object"
+                    )));
+                };
+                fields.insert("trait".to_string(), serde_json::Value::String(name));
+                traits.push(Trait::deserialize(body).map_err(de::Error::custom)?);
+            }
+            Ok(traits)
+        }
+    }
+
+    deserializer.deserialize_any(TraitsVisitor)
+}
+
+impl Trait {
+    /// Orders traits by `strength`, then by `r#trait` name; a NaN strength sorts lowest.
+    /// `id`, `expression_rules` and `category` are ignored.
+    pub fn cmp_by_strength(&self, other: &Trait) -> Ordering {
+        let strength = match (self.strength.is_nan(), other.strength.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self.strength.total_cmp(&other.strength),
+        };
+        strength.then_with(|| self.r#trait.cmp(&other.r#trait))
+    }
+}
+
+/// A trait keyed by [`Trait::cmp_by_strength`], for keeping traits in a `BTreeSet` or
+/// `BinaryHeap` by prominence.
+///
+/// Two wrapped traits are equal when their strength and name match, so a set keeps only
+/// one of several traits that differ only in id, expression rules or category.
+#[derive(Debug, Clone)]
+pub struct ByProminence(pub Trait);
+
+impl Ord for ByProminence {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_by_strength(&other.0)
+    }
+}
+
+impl PartialOrd for ByProminence {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ByProminence {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ByProminence {}
+
+/// The trait's `(name, strength)` pair.
+impl From<&Trait> for (String, f32) {
+    fn from(trait_: &Trait) -> Self {
+        (trait_.r#trait.clone(), trait_.strength)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TraitBuilderError {
+    TooManyRules { max: usize, count: usize },
+}
+
+impl std::fmt::Display for TraitBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraitBuilderError::TooManyRules { max, count } => {
+                write!(f, "{count} expression rules exceed the limit of {max}")
+            }
+        }
+    }
 }
 
+impl std::error::Error for TraitBuilderError {}
+
 #[derive(Default)]
 pub struct TraitBuilder {
+    id: Option<Uuid>,
     trait_name: String,
     strength: f32,
     expression_rules: Option<Vec<String>>,
+    category: Option<String>,
+    max_rules: Option<usize>,
 }
 
 impl TraitBuilder {
     pub fn new(name: &str) -> Self {
         Self {
+            id: None,
             trait_name: name.to_string(),
             strength: 0.5, // default strength
             expression_rules: None,
+            category: None,
+            max_rules: None,
         }
     }
 
+    /// Sets the server id, for rebuilding an existing trait in an update.
+    #[must_use]
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    #[must_use]
     pub fn strength(mut self, strength: f32) -> Self {
         self.strength = strength;
         self
     }
 
+    #[must_use]
     pub fn add_expression_rule(mut self, rule: &str) -> Self {
         if self.expression_rules.is_none() {
             self.expression_rules = Some(Vec::new());
@@ -42,23 +180,180 @@ impl TraitBuilder {
         self
     }
 
-    pub fn build(self) -> Trait {
-        Trait {
-            id: None,
+    #[must_use]
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    #[must_use]
+    pub fn clear_expression_rules(mut self) -> Self {
+        self.expression_rules = None;
+        self
+    }
+
+    #[must_use]
+    pub fn clear_category(mut self) -> Self {
+        self.category = None;
+        self
+    }
+
+    /// Makes `build` reject more than `max` expression rules (unlimited by default).
+    #[must_use]
+    pub fn max_rules(mut self, max: usize) -> Self {
+        self.max_rules = Some(max);
+        self
+    }
+
+    pub fn build(self) -> Result<Trait, TraitBuilderError> {
+        let count = self.expression_rules.as_ref().map_or(0, Vec::len);
+        if let Some(max) = self.max_rules.filter(|&max| count > max) {
+            return Err(TraitBuilderError::TooManyRules { max, count });
+        }
+
+        Ok(Trait {
+            id: self.id,
             r#trait: self.trait_name,
             strength: self.strength,
             expression_rules: self.expression_rules,
-        }
+            category: self.category,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::{BTreeSet, BinaryHeap};
+
+    #[test]
+    fn test_trait_legacy_name_field() {
+        let legacy: Trait =
+            serde_json::from_value(serde_json::json!({ "name": "curious", "strength": 0.7 }))
+                .unwrap();
+        let current: Trait =
+            serde_json::from_value(serde_json::json!({ "trait": "curious", "strength": 0.7 }))
+                .unwrap();
+        assert_eq!(legacy, current);
+        assert_eq!(serde_json::to_value(&legacy).unwrap()["trait"], "curious");
+    }
+
+    #[test]
+    fn test_trait_builder_id() {
+        let id = Uuid::new_v4();
+        let trait_ = TraitBuilder::new("curious")
+            .id(id)
+            .strength(0.7)
+            .build()
+            .unwrap();
+        assert_eq!(trait_.id, Some(id));
+        assert_eq!(
+            serde_json::to_value(&trait_).unwrap()["id"],
+            serde_json::json!(id.to_string())
+        );
+
+        let fresh = TraitBuilder::new("curious").build().unwrap();
+        assert!(serde_json::to_value(&fresh).unwrap().get("id").is_none());
+    }
+
+    #[test]
+    fn test_trait_ordering_in_btree_set() {
+        let mut traits: BTreeSet<ByProminence> = [
+            TraitBuilder::new("curious").strength(0.7).build().unwrap(),
+            TraitBuilder::new("bold").strength(0.9).build().unwrap(),
+            TraitBuilder::new("broken")
+                .strength(f32::NAN)
+                .build()
+                .unwrap(),
+            TraitBuilder::new("calm").strength(0.7).build().unwrap(),
+        ]
+        .into_iter()
+        .map(ByProminence)
+        .collect();
+
+        assert_eq!(traits.pop_last().unwrap().0.r#trait, "bold");
+        assert_eq!(traits.pop_last().unwrap().0.r#trait, "curious");
+        assert_eq!(traits.pop_last().unwrap().0.r#trait, "calm");
+        assert_eq!(traits.pop_last().unwrap().0.r#trait, "broken");
+        assert!(traits.is_empty());
+    }
+
+    #[test]
+    fn test_trait_ordering_in_binary_heap() {
+        let mut traits = BinaryHeap::from([
+            ByProminence(TraitBuilder::new("calm").strength(0.2).build().unwrap()),
+            ByProminence(TraitBuilder::new("bold").strength(0.9).build().unwrap()),
+        ]);
+        assert_eq!(traits.pop().unwrap().0.r#trait, "bold");
+        assert_eq!(traits.pop().unwrap().0.r#trait, "calm");
+    }
+
+    #[test]
+    fn test_trait_ordering_ignores_rules() {
+        let plain = TraitBuilder::new("warm").strength(0.5).build().unwrap();
+        let with_rule = TraitBuilder::new("warm")
+            .strength(0.5)
+            .add_expression_rule("smiles often")
+            .build()
+            .unwrap();
+        assert_ne!(plain, with_rule);
+        assert_eq!(plain.cmp_by_strength(&with_rule), Ordering::Equal);
+        assert_eq!(ByProminence(plain), ByProminence(with_rule));
+
+        let meek = TraitBuilder::new("meek").strength(-1.0).build().unwrap();
+        let nan = TraitBuilder::new("nan").strength(f32::NAN).build().unwrap();
+        assert_eq!(meek.cmp_by_strength(&nan), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_trait_builder_max_rules() {
+        let within = TraitBuilder::new("chatty")
+            .max_rules(2)
+            .add_expression_rule("asks follow-ups")
+            .add_expression_rule("shares anecdotes")
+            .build()
+            .unwrap();
+        assert_eq!(within.expression_rules.unwrap().len(), 2);
+
+        let over = TraitBuilder::new("chatty")
+            .add_expression_rule("asks follow-ups")
+            .add_expression_rule("shares anecdotes")
+            .add_expression_rule("uses emoji")
+            .max_rules(2)
+            .build();
+        assert_eq!(
+            over.unwrap_err(),
+            TraitBuilderError::TooManyRules { max: 2, count: 3 }
+        );
+        assert_eq!(
+            TraitBuilderError::TooManyRules { max: 2, count: 3 }.to_string(),
+            "3 expression rules exceed the limit of 2"
+        );
+
+        let unlimited = (0..50)
+            .fold(TraitBuilder::new("verbose"), |builder, _| {
+                builder.add_expression_rule("says more")
+            })
+            .build();
+        assert!(unlimited.is_ok());
+    }
+
+    #[test]
+    fn test_trait_builder_clear_optional_fields() {
+        let trait_ = TraitBuilder::new("curious")
+            .add_expression_rule("asks questions")
+            .category("cognitive")
+            .clear_expression_rules()
+            .clear_category()
+            .build()
+            .unwrap();
+        assert!(trait_.expression_rules.is_none());
+        assert!(trait_.category.is_none());
+    }
 
     #[test]
     fn test_trait_builder_default_values() {
-        let trait_ = TraitBuilder::new("friendly").build();
+        let trait_ = TraitBuilder::new("friendly").build().unwrap();
 
         assert_eq!(trait_.r#trait, "friendly");
         assert_eq!(trait_.strength, 0.5); // Check default strength
@@ -67,7 +362,10 @@ mod tests {
 
     #[test]
     fn test_trait_builder_with_strength() {
-        let trait_ = TraitBuilder::new("intelligent").strength(0.8).build();
+        let trait_ = TraitBuilder::new("intelligent")
+            .strength(0.8)
+            .build()
+            .unwrap();
 
         assert_eq!(trait_.r#trait, "intelligent");
         assert_eq!(trait_.strength, 0.8);
@@ -78,7 +376,8 @@ mod tests {
     fn test_trait_builder_with_single_rule() {
         let trait_ = TraitBuilder::new("helpful")
             .add_expression_rule("always offers assistance")
-            .build();
+            .build()
+            .unwrap();
 
         assert_eq!(trait_.r#trait, "helpful");
         assert_eq!(trait_.strength, 0.5);
@@ -94,7 +393,8 @@ mod tests {
             .add_expression_rule("thinks outside the box")
             .add_expression_rule("provides unique solutions")
             .add_expression_rule("explores new possibilities")
-            .build();
+            .build()
+            .unwrap();
 
         let rules = trait_.expression_rules.unwrap();
         assert_eq!(rules.len(), 3);
@@ -109,7 +409,8 @@ mod tests {
             .strength(0.9)
             .add_expression_rule("listens carefully")
             .add_expression_rule("shows understanding")
-            .build();
+            .build()
+            .unwrap();
 
         assert_eq!(trait_.r#trait, "empathetic");
         assert_eq!(trait_.strength, 0.9);
@@ -120,17 +421,53 @@ mod tests {
         assert_eq!(rules[1], "shows understanding");
     }
 
+    #[test]
+    fn test_trait_category_serde() {
+        let legacy: Trait =
+            serde_json::from_value(serde_json::json!({ "trait": "kind", "strength": 0.5 }))
+                .unwrap();
+        assert!(legacy.category.is_none());
+        assert!(serde_json::to_value(&legacy)
+            .unwrap()
+            .get("category")
+            .is_none());
+
+        let grouped = TraitBuilder::new("curious")
+            .category("cognitive")
+            .build()
+            .unwrap();
+        let value = serde_json::to_value(&grouped).unwrap();
+        assert_eq!(value["category"], "cognitive");
+        assert_eq!(serde_json::from_value::<Trait>(value).unwrap(), grouped);
+    }
+
+    #[test]
+    fn test_trait_strength_serializes_without_float_tail() {
+        let trait_ = TraitBuilder::new("sarcastic")
+            .strength(0.9)
+            .build()
+            .unwrap();
+
+        let value = serde_json::to_value(&trait_).unwrap();
+        assert_eq!(value["strength"], serde_json::json!(0.9));
+        assert!(serde_json::to_string(&value)
+            .unwrap()
+            .contains(r#""strength":0.9"#));
+    }
+
     #[test]
     fn test_trait_builder_method_chaining_order() {
         let trait1 = TraitBuilder::new("adaptable")
             .strength(0.7)
             .add_expression_rule("rule1")
-            .build();
+            .build()
+            .unwrap();
 
         let trait2 = TraitBuilder::new("adaptable")
             .add_expression_rule("rule1")
             .strength(0.7)
-            .build();
+            .build()
+            .unwrap();
 
         assert_eq!(trait1, trait2);
     }
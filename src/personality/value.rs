@@ -3,9 +3,17 @@ use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Value {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub name: String,
+    #[serde(
+        serialize_with = "crate::precision::serialize",
+        deserialize_with = "crate::number::deserialize_unit"
+    )]
     pub importance: f32,
     pub expression: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -14,7 +22,11 @@ pub struct Value {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ValueConflict {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub value: String,
     pub resolution: String,
@@ -38,6 +50,25 @@ impl Value {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum ValueBuilderError {
+    MissingName,
+    MissingImportance,
+    MissingExpression,
+}
+
+impl std::fmt::Display for ValueBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueBuilderError::MissingName => write!(f, "name is required"),
+            ValueBuilderError::MissingImportance => write!(f, "importance is required"),
+            ValueBuilderError::MissingExpression => write!(f, "expression is required"),
+        }
+    }
+}
+
+impl std::error::Error for ValueBuilderError {}
+
 #[derive(Default)]
 pub struct ValueBuilder {
     name: Option<String>,
@@ -51,33 +82,47 @@ impl ValueBuilder {
         Self::default()
     }
 
+    #[must_use]
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
         self
     }
 
+    #[must_use]
     pub fn importance(mut self, importance: f32) -> Self {
         let clamped = importance.clamp(0.0, 1.0);
         self.importance = Some(clamped);
         self
     }
 
+    #[must_use]
     pub fn expression(mut self, expression: impl Into<String>) -> Self {
         self.expression = Some(expression.into());
         self
     }
 
+    #[must_use]
     pub fn conflicts(mut self, conflicts: Vec<ValueConflict>) -> Self {
         self.conflicts = Some(conflicts);
         self
     }
 
-    pub fn build(self) -> Result<Value, &'static str> {
+    #[must_use]
+    pub fn clear_conflicts(mut self) -> Self {
+        self.conflicts = None;
+        self
+    }
+
+    pub fn build(self) -> Result<Value, ValueBuilderError> {
         Ok(Value {
             id: None,
-            name: self.name.ok_or("name is required")?,
-            importance: self.importance.ok_or("importance is required")?,
-            expression: self.expression.ok_or("expression is required")?,
+            name: self.name.ok_or(ValueBuilderError::MissingName)?,
+            importance: self
+                .importance
+                .ok_or(ValueBuilderError::MissingImportance)?,
+            expression: self
+                .expression
+                .ok_or(ValueBuilderError::MissingExpression)?,
             conflicts: self.conflicts,
         })
     }
@@ -125,6 +170,23 @@ mod tests {
         assert!(value.id.is_some());
     }
 
+    #[test]
+    fn test_value_builder_clear_conflicts() {
+        let value = ValueBuilder::new()
+            .name("honesty")
+            .importance(0.9)
+            .expression("always tells the truth")
+            .conflicts(vec![ValueConflict {
+                id: None,
+                value: "kindness".to_string(),
+                resolution: "tactful truth".to_string(),
+            }])
+            .clear_conflicts()
+            .build()
+            .unwrap();
+        assert!(value.conflicts.is_none());
+    }
+
     #[test]
     fn test_value_builder() {
         let value = ValueBuilder::new()
@@ -165,12 +227,14 @@ mod tests {
     #[test]
     fn test_value_builder_validation() {
         let result = ValueBuilder::new().build();
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), ValueBuilderError::MissingName);
 
         let result = ValueBuilder::new().name("test").build();
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), ValueBuilderError::MissingImportance);
 
         let result = ValueBuilder::new().name("test").importance(0.5).build();
-        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err, ValueBuilderError::MissingExpression);
+        assert_eq!(err.to_string(), "expression is required");
     }
 }
@@ -1,9 +1,20 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+const QUALITIES_WEIGHT: f32 = 0.35;
+const PATTERNS_WEIGHT: f32 = 0.35;
+const STYLE_WEIGHT: f32 = 0.15;
+const TONE_WEIGHT: f32 = 0.15;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Voice {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub style: String,
     pub tone: String,
@@ -35,8 +46,64 @@ impl Voice {
     pub fn builder() -> VoiceBuilder {
         VoiceBuilder::default()
     }
+
+    /// Scores how alike two voices are, from `0.0` (nothing shared) to `1.0` (identical).
+    ///
+    /// The score weights the Jaccard overlap of `qualities` and of `patterns` at 0.35 each,
+    /// and exact `style` and `tone` matches at 0.15 each. Strings are compared trimmed and
+    /// case-insensitively.
+    pub fn similarity(&self, other: &Voice) -> f32 {
+        let exact = |a: &str, b: &str| {
+            if normalize(a) == normalize(b) {
+                1.0
+            } else {
+                0.0
+            }
+        };
+
+        QUALITIES_WEIGHT * jaccard(&self.qualities, &other.qualities)
+            + PATTERNS_WEIGHT * jaccard(&self.patterns, &other.patterns)
+            + STYLE_WEIGHT * exact(&self.style, &other.style)
+            + TONE_WEIGHT * exact(&self.tone, &other.tone)
+    }
+}
+
+fn normalize(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+/// Jaccard index of two string lists; two empty lists are considered identical.
+fn jaccard(a: &[String], b: &[String]) -> f32 {
+    let a: HashSet<String> = a.iter().map(|s| normalize(s)).collect();
+    let b: HashSet<String> = b.iter().map(|s| normalize(s)).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VoiceBuilderError {
+    MissingStyle,
+    MissingTone,
+    MissingQualities,
+    MissingPatterns,
+}
+
+impl std::fmt::Display for VoiceBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoiceBuilderError::MissingStyle => write!(f, "style is required"),
+            VoiceBuilderError::MissingTone => write!(f, "tone is required"),
+            VoiceBuilderError::MissingQualities => write!(f, "qualities is required"),
+            VoiceBuilderError::MissingPatterns => write!(f, "patterns is required"),
+        }
+    }
 }
 
+impl std::error::Error for VoiceBuilderError {}
+
 #[derive(Default)]
 pub struct VoiceBuilder {
     style: Option<String>,
@@ -50,33 +117,37 @@ impl VoiceBuilder {
         Self::default()
     }
 
+    #[must_use]
     pub fn style(mut self, style: impl Into<String>) -> Self {
         self.style = Some(style.into());
         self
     }
 
+    #[must_use]
     pub fn tone(mut self, tone: impl Into<String>) -> Self {
         self.tone = Some(tone.into());
         self
     }
 
+    #[must_use]
     pub fn qualities(mut self, qualities: Vec<String>) -> Self {
         self.qualities = Some(qualities);
         self
     }
 
+    #[must_use]
     pub fn patterns(mut self, patterns: Vec<String>) -> Self {
         self.patterns = Some(patterns);
         self
     }
 
-    pub fn build(self) -> Result<Voice, &'static str> {
+    pub fn build(self) -> Result<Voice, VoiceBuilderError> {
         Ok(Voice {
             id: None,
-            style: self.style.ok_or("style is required")?,
-            tone: self.tone.ok_or("tone is required")?,
-            qualities: self.qualities.ok_or("qualities is required")?,
-            patterns: self.patterns.ok_or("patterns is required")?,
+            style: self.style.ok_or(VoiceBuilderError::MissingStyle)?,
+            tone: self.tone.ok_or(VoiceBuilderError::MissingTone)?,
+            qualities: self.qualities.ok_or(VoiceBuilderError::MissingQualities)?,
+            patterns: self.patterns.ok_or(VoiceBuilderError::MissingPatterns)?,
         })
     }
 }
@@ -84,6 +155,7 @@ impl VoiceBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
     use serde_json::json;
 
     #[test]
@@ -155,20 +227,61 @@ mod tests {
     #[test]
     fn test_voice_builder_validation() {
         let result = VoiceBuilder::new().build();
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), VoiceBuilderError::MissingStyle);
 
         let result = VoiceBuilder::new().style("casual").build();
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), VoiceBuilderError::MissingTone);
 
         let result = VoiceBuilder::new().style("casual").tone("friendly").build();
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), VoiceBuilderError::MissingQualities);
 
         let result = VoiceBuilder::new()
             .style("casual")
             .tone("friendly")
             .qualities(vec!["warm".to_string()])
             .build();
-        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err, VoiceBuilderError::MissingPatterns);
+        assert_eq!(err.to_string(), "patterns is required");
+    }
+
+    #[test]
+    fn test_voice_similarity_identical() {
+        let voice = Voice::default();
+        let mut shouted = voice.clone();
+        shouted.style = shouted.style.to_uppercase();
+        shouted.qualities = shouted.qualities.iter().map(|q| q.to_uppercase()).collect();
+
+        assert_relative_eq!(voice.similarity(&voice), 1.0);
+        assert_relative_eq!(voice.similarity(&shouted), 1.0);
+    }
+
+    #[test]
+    fn test_voice_similarity_disjoint() {
+        let other = Voice {
+            id: None,
+            style: "casual".to_string(),
+            tone: "friendly".to_string(),
+            qualities: vec!["warm".to_string()],
+            patterns: vec!["uses metaphors".to_string()],
+        };
+
+        assert!(Voice::default().similarity(&other) < 0.01);
+    }
+
+    #[test]
+    fn test_voice_similarity_partial_overlap() {
+        let voice = Voice::default();
+        let other = Voice {
+            style: "casual".to_string(),
+            qualities: vec!["commanding".to_string(), "warm".to_string()],
+            ..voice.clone()
+        };
+
+        // qualities 1/4, patterns 3/3, tone matches, style differs
+        let expected = 0.35 * 0.25 + 0.35 + 0.15;
+        assert_relative_eq!(voice.similarity(&other), expected, epsilon = 1e-6);
+        assert_relative_eq!(other.similarity(&voice), expected, epsilon = 1e-6);
     }
 
     #[test]
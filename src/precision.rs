@@ -0,0 +1,93 @@
+//! Rounding applied when serializing `f32` scores such as trait strengths.
+//!
+//! Widening an `f32` to a JSON number can expose its binary representation
+//! (`0.9` becomes `0.8999999761581421`), so scores are rounded to a fixed number
+//! of decimals before they are emitted. Request bodies use the client's
+//! [`SoulgraphBuilder::float_decimals`](crate::SoulgraphBuilder::float_decimals);
+//! everything else uses [`DEFAULT_DECIMALS`], except in-place edits such as
+//! `Memory::apply_json_patch`, which keep scores exact.
+
+use std::cell::Cell;
+
+use serde::Serializer;
+
+pub const DEFAULT_DECIMALS: u32 = 4;
+
+thread_local! {
+    /// `None` while serializing exactly.
+    static DECIMALS: Cell<Option<u32>> = const { Cell::new(Some(DEFAULT_DECIMALS)) };
+}
+
+/// Restores the previous precision when a scope ends, even by panic.
+struct Restore(Option<u32>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        DECIMALS.with(|decimals| decimals.set(self.0));
+    }
+}
+
+/// Runs `f` with scores serialized on this thread rounded to `decimals`.
+pub(crate) fn with_decimals<R>(decimals: u32, f: impl FnOnce() -> R) -> R {
+    scoped(Some(decimals), f)
+}
+
+/// Runs `f` with scores serialized on this thread unrounded, so they deserialize back to
+/// the same `f32`.
+pub(crate) fn exact<R>(f: impl FnOnce() -> R) -> R {
+    scoped(None, f)
+}
+
+fn scoped<R>(decimals: Option<u32>, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(DECIMALS.with(|current| current.replace(decimals)));
+    f()
+}
+
+pub(crate) fn serialize<S>(value: &f32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match DECIMALS.with(Cell::get) {
+        Some(decimals) => serializer.serialize_f64(round(*value, decimals)),
+        None => serializer.serialize_f32(*value),
+    }
+}
+
+fn round(value: f32, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals.min(f64::DIGITS) as i32);
+    (f64::from(value) * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::personality::traits::TraitBuilder;
+
+    #[test]
+    fn test_default_decimals() {
+        assert_eq!(DEFAULT_DECIMALS, 4);
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(round(0.9, 4), 0.9);
+        assert_eq!(round(0.123456, 4), 0.1235);
+        assert_eq!(round(0.123456, 2), 0.12);
+        assert_eq!(round(-0.55, 1), -0.6);
+    }
+
+    #[test]
+    fn test_with_decimals_is_scoped() {
+        let trait_ = TraitBuilder::new("precise")
+            .strength(0.123456)
+            .build()
+            .unwrap();
+        let scoped = with_decimals(2, || serde_json::to_value(&trait_).unwrap());
+        assert_eq!(scoped["strength"], 0.12);
+        assert_eq!(serde_json::to_value(&trait_).unwrap()["strength"], 0.1235);
+
+        let exact = exact(|| serde_json::to_value(&trait_).unwrap());
+        let back: crate::personality::traits::Trait = serde_json::from_value(exact).unwrap();
+        assert_eq!(back.strength, 0.123456);
+    }
+}
@@ -0,0 +1,64 @@
+use reqwest::header::HeaderMap;
+
+const LIMIT_HEADER: &str = "x-ratelimit-limit";
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// Rate-limit state reported by the server through `X-RateLimit-*` headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed in the current window.
+    pub limit: u64,
+    /// Requests left in the current window.
+    pub remaining: u64,
+    /// Unix timestamp (seconds) at which the window resets.
+    pub reset_at: i64,
+}
+
+impl RateLimit {
+    /// Parses the rate-limit headers, returning `None` unless all three are present and valid.
+    pub fn from_headers(headers: &HeaderMap) -> Option<RateLimit> {
+        Some(RateLimit {
+            limit: parse_header(headers, LIMIT_HEADER)?,
+            remaining: parse_header(headers, REMAINING_HEADER)?,
+            reset_at: parse_header(headers, RESET_HEADER)?,
+        })
+    }
+}
+
+fn parse_header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "42".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "1736553600".parse().unwrap());
+
+        assert_eq!(
+            RateLimit::from_headers(&headers),
+            Some(RateLimit {
+                limit: 100,
+                remaining: 42,
+                reset_at: 1736553600,
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_headers_incomplete() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "many".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "1736553600".parse().unwrap());
+
+        assert_eq!(RateLimit::from_headers(&headers), None);
+        assert_eq!(RateLimit::from_headers(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,316 @@
+use reqwest::{header::CONTENT_TYPE, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Format, SoulgraphError};
+
+/// Maximum number of bytes kept from a non-JSON error body.
+const NON_JSON_BODY_LIMIT: usize = 512;
+
+/// Server confirmation returned by the `delete` endpoints.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct DeleteResult {
+    pub id: Uuid,
+    pub deleted: bool,
+}
+
+/// Envelope of a GraphQL response.
+#[derive(Debug, Deserialize)]
+pub(crate) struct GraphQlResponse {
+    #[serde(default)]
+    pub data: serde_json::Value,
+    #[serde(default)]
+    pub errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct GraphQlError {
+    pub message: String,
+}
+
+/// Marks a response as wrapped in a `{ "data": ..., "meta": ... }` envelope.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Envelope;
+
+#[derive(Deserialize)]
+struct Enveloped<T> {
+    data: T,
+    #[serde(default)]
+    meta: Option<serde_json::Value>,
+}
+
+/// Fails with `SoulgraphError::Status` when the response is not a success.
+///
+/// Error bodies that are not JSON (e.g. a proxy's HTML error page) are captured as
+/// text, truncated to the first 512 bytes.
+pub(crate) async fn check_status(response: Response) -> Result<Response, SoulgraphError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("json"));
+    let bytes = response.bytes().await.unwrap_or_default();
+    let body = if is_json {
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else {
+        String::from_utf8_lossy(&bytes[..bytes.len().min(NON_JSON_BODY_LIMIT)]).into_owned()
+    };
+    Err(SoulgraphError::Status { status, body })
+}
+
+/// Decodes a successful response into `T`, in the format named by its `Content-Type`.
+///
+/// Responses marked with an [`Envelope`] are decoded from their `data` field.
+pub(crate) async fn decode<T: DeserializeOwned>(response: Response) -> Result<T, SoulgraphError> {
+    decode_with_meta(response).await.map(|(data, _)| data)
+}
+
+/// Like [`decode`], but also returns the envelope's `meta` field, if there was one.
+pub(crate) async fn decode_with_meta<T: DeserializeOwned>(
+    response: Response,
+) -> Result<(T, Option<serde_json::Value>), SoulgraphError> {
+    let enveloped = response.extensions().get::<Envelope>().is_some();
+    let response = check_status(response).await?;
+    let format = body_format(&response);
+    let body = response.bytes().await?;
+    if enveloped {
+        let Enveloped { data, meta } = format.decode(&body)?;
+        Ok((data, meta))
+    } else {
+        Ok((format.decode(&body)?, None))
+    }
+}
+
+/// Like [`decode`], but ignores any [`Envelope`], for payloads with their own framing.
+pub(crate) async fn decode_bare<T: DeserializeOwned>(
+    response: Response,
+) -> Result<T, SoulgraphError> {
+    let response = check_status(response).await?;
+    let format = body_format(&response);
+    let body = response.bytes().await?;
+    format.decode(&body)
+}
+
+fn body_format(response: &Response) -> Format {
+    Format::from_content_type(
+        response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok()),
+    )
+}
+
+/// Maps a HEAD response to whether the resource exists: `true` on success, `false` on 404.
+pub(crate) async fn exists(response: Response) -> Result<bool, SoulgraphError> {
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+    check_status(response).await.map(|_| true)
+}
+
+/// Parses a delete confirmation, synthesizing one for `id` when the body is empty.
+///
+/// Responses marked with an [`Envelope`] are decoded from their `data` field.
+pub(crate) async fn delete_result(
+    response: Response,
+    id: &str,
+) -> Result<DeleteResult, SoulgraphError> {
+    let enveloped = response.extensions().get::<Envelope>().is_some();
+    let response = check_status(response).await?;
+    let status = response.status();
+    let format = body_format(&response);
+    let body = response.bytes().await?;
+
+    if status == StatusCode::NO_CONTENT || body.is_empty() {
+        return Ok(DeleteResult {
+            id: Uuid::parse_str(id)?,
+            deleted: true,
+        });
+    }
+
+    if enveloped {
+        let Enveloped { data, .. } = format.decode(&body)?;
+        Ok(data)
+    } else {
+        format.decode(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Soul, Soulgraph};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_html_error_body_is_captured_as_text() {
+        let server = MockServer::start().await;
+        let page = format!(
+            "<html><body><h1>502 Bad Gateway</h1>{}</body></html>",
+            "x".repeat(1024)
+        );
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(502).set_body_raw(page, "text/html"))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let err = Soul::get("id", &soulgraph).await.unwrap_err();
+
+        let SoulgraphError::Status { status, body } = err else {
+            panic!("expected a status error, got {err:?}");
+        };
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert!(body.starts_with("<html><body><h1>502 Bad Gateway</h1>"));
+        assert_eq!(body.len(), NON_JSON_BODY_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_json_error_body_is_kept_whole() {
+        let server = MockServer::start().await;
+        let message = "y".repeat(1024);
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(serde_json::json!({ "error": message })),
+            )
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let err = Soul::get("id", &soulgraph).await.unwrap_err();
+
+        let SoulgraphError::Status { body, .. } = err else {
+            panic!("expected a status error, got {err:?}");
+        };
+        assert!(body.contains(&message));
+    }
+
+    #[tokio::test]
+    async fn test_enveloped_and_bare_bodies() {
+        let soul = Soul::default();
+        let server = MockServer::start().await;
+        Mock::given(path("/personality/wrapped"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": soul,
+                "meta": { "request_id": "r-1" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/personality/bare"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&soul))
+            .mount(&server)
+            .await;
+
+        let enveloped = Soulgraph::builder()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .envelope(true)
+            .build();
+        assert_eq!(Soul::get("wrapped", &enveloped).await.unwrap(), soul);
+        let (data, meta) = enveloped
+            .get_with_meta::<Soul>("/personality/wrapped")
+            .await
+            .unwrap();
+        assert_eq!(data, soul);
+        assert_eq!(meta, Some(serde_json::json!({ "request_id": "r-1" })));
+
+        // Each call gets its own response's meta, however the calls interleave.
+        Mock::given(path("/personality/other"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": soul,
+                "meta": { "request_id": "r-2" }
+            })))
+            .mount(&server)
+            .await;
+        let (first, second) = futures::join!(
+            enveloped.get_with_meta::<Soul>("/personality/wrapped"),
+            enveloped.get_with_meta::<Soul>("/personality/other"),
+        );
+        assert_eq!(first.unwrap().1.unwrap()["request_id"], "r-1");
+        assert_eq!(second.unwrap().1.unwrap()["request_id"], "r-2");
+        assert!(matches!(
+            Soul::get("bare", &enveloped).await,
+            Err(SoulgraphError::Decode(_))
+        ));
+
+        let bare = Soulgraph::new("test-key", &server.uri());
+        assert_eq!(Soul::get("bare", &bare).await.unwrap(), soul);
+        let (data, meta) = bare
+            .get_with_meta::<Soul>("/personality/bare")
+            .await
+            .unwrap();
+        assert_eq!(data, soul);
+        assert!(meta.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enveloped_delete_result() {
+        let id = Uuid::new_v4();
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "id": id, "deleted": true },
+                "meta": { "request_id": "r-1" }
+            })))
+            .mount(&server)
+            .await;
+
+        let enveloped = Soulgraph::builder()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .envelope(true)
+            .build();
+        assert_eq!(
+            Soul::delete(&id.to_string(), &enveloped).await.unwrap(),
+            DeleteResult { id, deleted: true }
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn test_message_pack_soul_body_is_decoded() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        let soul = Soul::default();
+        Mock::given(method("GET"))
+            .and(header("accept", "application/msgpack"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                rmp_serde::to_vec_named(&soul).unwrap(),
+                "application/msgpack",
+            ))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::builder()
+            .api_key("test-key")
+            .base_url(&server.uri())
+            .accept(Format::MessagePack)
+            .build();
+        assert_eq!(Soul::get("id", &soulgraph).await.unwrap(), soul);
+    }
+
+    #[tokio::test]
+    async fn test_json_body_is_decoded_by_default() {
+        use wiremock::matchers::header;
+
+        let server = MockServer::start().await;
+        let soul = Soul::default();
+        Mock::given(method("GET"))
+            .and(header("accept", "application/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&soul))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        assert_eq!(Soul::get("id", &soulgraph).await.unwrap(), soul);
+    }
+}
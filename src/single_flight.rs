@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+use reqwest::{header::HeaderMap, Response, ResponseBuilderExt, StatusCode, Url, Version};
+
+use crate::SoulgraphError;
+
+/// A response read into memory so every waiter can get its own copy.
+#[derive(Debug)]
+struct BufferedResponse {
+    url: Url,
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl BufferedResponse {
+    async fn read(response: Response) -> Result<BufferedResponse, SoulgraphError> {
+        Ok(BufferedResponse {
+            url: response.url().clone(),
+            status: response.status(),
+            version: response.version(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+
+    fn to_response(&self) -> Response {
+        let mut response = http::Response::builder()
+            .url(self.url.clone())
+            .status(self.status)
+            .version(self.version)
+            .body(self.body.clone())
+            .expect("status and version came from a valid response");
+        *response.headers_mut() = self.headers.clone();
+        Response::from(response)
+    }
+}
+
+/// `None` when the leading request failed; errors are not shared.
+type Flight = Shared<oneshot::Receiver<Option<Arc<BufferedResponse>>>>;
+
+/// Coalesces concurrent identical requests so only one of them reaches the server.
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Mutex<HashMap<String, Flight>>,
+}
+
+/// Removes the flight when the leading request finishes or is dropped.
+struct Landing<'a> {
+    single_flight: &'a SingleFlight,
+    key: &'a str,
+}
+
+impl Drop for Landing<'_> {
+    fn drop(&mut self) {
+        self.single_flight
+            .in_flight
+            .lock()
+            .unwrap()
+            .remove(self.key);
+    }
+}
+
+impl SingleFlight {
+    /// Runs `send` unless a request for `key` is already in flight, in which case its
+    /// response is shared instead. If that request fails or is cancelled, `send` runs after
+    /// all.
+    pub(crate) async fn run<F>(&self, key: String, send: F) -> Result<Response, SoulgraphError>
+    where
+        F: Future<Output = Result<Response, SoulgraphError>>,
+    {
+        let leader = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(flight) => Err(flight.clone()),
+                None => {
+                    let (sender, receiver) = oneshot::channel();
+                    in_flight.insert(key.clone(), receiver.shared());
+                    Ok(sender)
+                }
+            }
+        };
+
+        let sender = match leader {
+            Ok(sender) => sender,
+            Err(flight) => {
+                return match flight.await {
+                    Ok(Some(buffered)) => Ok(buffered.to_response()),
+                    _ => send.await,
+                };
+            }
+        };
+
+        let landing = Landing {
+            single_flight: self,
+            key: &key,
+        };
+        let buffered = match send.await {
+            Ok(response) => BufferedResponse::read(response).await.map(Arc::new),
+            Err(err) => Err(err),
+        };
+        drop(landing);
+        let _ = sender.send(buffered.as_ref().ok().cloned());
+        Ok(buffered?.to_response())
+    }
+}
@@ -1,30 +1,156 @@
-use std::{error, fmt};
-
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fs, io, path::Path};
 use uuid::Uuid;
 
-use crate::{entity, personality, Soulgraph};
+use crate::{
+    entity, personality,
+    response::{self, DeleteResult},
+    Soulgraph, SoulgraphError,
+};
 
 const DEFAULT_VERSION: &str = "1.0";
+const MAX_CONCURRENCY: usize = 32;
+const VOLATILE_METADATA: [&str; 2] = ["creation_date", "last_modified"];
+/// An upgrade from one normalized version to the next.
+type Migration = (&'static str, &'static str, fn(&mut Soul));
+/// Upgrades applied in turn by [`Soul::migrate`]; none yet, as 1.0 is the only schema.
+const MIGRATIONS: &[Migration] = &[];
+/// Traits weaker than this are reported by [`Soul::lint`] as probable noise.
+const WEAK_TRAIT_STRENGTH: f32 = 0.1;
+/// Voices with fewer qualities than this are reported by [`Soul::lint`].
+const MIN_VOICE_QUALITIES: usize = 2;
+/// Traits at least this strong count as dominant in [`Soul::consistency_score`].
+const DOMINANT_TRAIT_STRENGTH: f32 = 0.7;
+/// Weights of the three [`Soul::consistency_score`] penalties; they sum to one.
+const UNRESOLVED_CONFLICT_WEIGHT: f32 = 0.4;
+const EMPTY_RULES_WEIGHT: f32 = 0.3;
+const TONE_CONTRADICTION_WEIGHT: f32 = 0.3;
 
-#[derive(Debug)]
-pub struct CorruptSoul;
-
-impl fmt::Display for CorruptSoul {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "personality is corrupted")
-    }
-}
-
-impl error::Error for CorruptSoul {}
+/// `(trait, tone word)` pairs where a voice tone containing the word contradicts a dominant
+/// trait of that name; the default mapping of [`Soul::consistency_score`].
+pub const DEFAULT_TONE_CONTRADICTIONS: &[(&str, &str)] = &[
+    ("sarcastic", "earnest"),
+    ("playful", "solemn"),
+    ("calm", "frantic"),
+    ("patient", "impatient"),
+    ("warm", "cold"),
+    ("friendly", "hostile"),
+    ("optimistic", "pessimistic"),
+    ("professional", "crude"),
+    ("tough-love", "gentle"),
+];
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Soul {
     pub version: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::id::deserialize"
+    )]
     pub id: Option<Uuid>,
     pub entity: entity::Entity,
     pub personality: personality::Personality,
+    /// Top-level keys not modeled above, kept so they survive a fetch/modify/update cycle.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Headline counts of a soul for dashboards; see [`Soul::summary`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SoulSummary {
+    pub trait_count: usize,
+    pub value_count: usize,
+    pub has_voice: bool,
+    pub boundary_count: usize,
+    /// Mean trait strength, or `None` when the personality has no traits.
+    pub average_trait_strength: Option<f32>,
+}
+
+/// A non-fatal advisory about a soul definition, produced by [`Soul::lint`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintWarning {
+    /// A trait so weak it probably has no effect.
+    WeakTrait { name: String, strength: f32 },
+    /// A trait whose expression-rule list is present but empty.
+    EmptyExpressionRules(String),
+    /// A value with a blank `expression`.
+    EmptyValueExpression(String),
+    /// A voice with fewer than two qualities; carries how many it has.
+    SparseVoiceQualities(usize),
+    /// A personality without any values.
+    NoValues,
+}
+
+impl std::fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintWarning::WeakTrait { name, strength } => {
+                write!(
+                    f,
+                    "trait \"{name}\" has strength {strength}, which is probably noise"
+                )
+            }
+            LintWarning::EmptyExpressionRules(name) => {
+                write!(f, "trait \"{name}\" has an empty expression-rule list")
+            }
+            LintWarning::EmptyValueExpression(name) => {
+                write!(f, "value \"{name}\" has an empty expression")
+            }
+            LintWarning::SparseVoiceQualities(count) => {
+                write!(f, "voice has {count} qualities; at least 2 are recommended")
+            }
+            LintWarning::NoValues => write!(f, "personality has no values"),
+        }
+    }
+}
+
+/// Why [`Soul::migrate`] could not upgrade a soul.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// The version string is not a dotted number, e.g. `"latest"`.
+    InvalidVersion(String),
+    /// No chain of migrations leads from this version to the current one.
+    UnsupportedVersion(String),
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::InvalidVersion(version) => {
+                write!(f, "\"{version}\" is not a valid version")
+            }
+            MigrationError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "cannot migrate from version {version} to {DEFAULT_VERSION}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// Normalizes `"v1"`, `"1.0.0"` and the like to `major.minor`, e.g. `"1.0"`.
+fn normalize_version(version: &str) -> Result<String, MigrationError> {
+    let invalid = || MigrationError::InvalidVersion(version.to_string());
+    let parts = version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if parts.len() > 2 && parts[2..].iter().any(|&part| part != 0) {
+        return Err(MigrationError::UnsupportedVersion(version.to_string()));
+    }
+    Ok(format!(
+        "{}.{}",
+        parts[0],
+        parts.get(1).copied().unwrap_or(0)
+    ))
 }
 
 impl Default for Soul {
@@ -34,6 +160,7 @@ impl Default for Soul {
             version: DEFAULT_VERSION.to_string(),
             entity: entity::Entity::default(),
             personality: personality::Personality::default(),
+            extra: HashMap::new(),
         }
     }
 }
@@ -44,46 +171,314 @@ impl Soul {
         SoulBuilder::default()
     }
 
-    /// Get the `Soul` with the given `id`.
-    pub async fn get(id: &str, soul: Soulgraph) -> Result<Soul, CorruptSoul> {
-        if let Ok(response) = soul.get(format!("/personality/{id}").as_str()).await {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Soul>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptSoul),
-                },
-                _ => Err(CorruptSoul),
+    /// Upgrades the soul from its `version` to the current schema version, applying each
+    /// registered migration in turn and then setting `version` to the current one.
+    ///
+    /// Equivalent spellings such as `"1.0.0"` are normalized first. The soul is left
+    /// untouched when no migration path exists.
+    pub fn migrate(&mut self) -> Result<(), MigrationError> {
+        let mut version = normalize_version(&self.version)?;
+        let mut steps = Vec::new();
+        while version != DEFAULT_VERSION {
+            let (_, to, step) = MIGRATIONS
+                .iter()
+                .find(|(from, _, _)| *from == version)
+                .ok_or_else(|| MigrationError::UnsupportedVersion(self.version.clone()))?;
+            steps.push(step);
+            version = to.to_string();
+        }
+
+        for step in steps {
+            step(self);
+        }
+        self.version = DEFAULT_VERSION.to_string();
+        Ok(())
+    }
+
+    /// Clones the soul as a new instance of it as a template: the soul, entity, personality
+    /// and every trait, value, value conflict, voice, relationship and boundary get a fresh
+    /// id, while all other content is kept.
+    pub fn instantiate(&self) -> Soul {
+        let mut soul = self.clone();
+        soul.id = Some(Uuid::new_v4());
+        soul.entity.id = Some(Uuid::new_v4());
+
+        let personality = &mut soul.personality;
+        personality.id = Some(Uuid::new_v4());
+        for trait_ in &mut personality.traits {
+            trait_.id = Some(Uuid::new_v4());
+        }
+        for value in personality.values.iter_mut().flatten() {
+            value.id = Some(Uuid::new_v4());
+            for conflict in value.conflicts.iter_mut().flatten() {
+                conflict.id = Some(Uuid::new_v4());
             }
-        } else {
-            Err(CorruptSoul)
         }
+        if let Some(voice) = &mut personality.voice {
+            voice.id = Some(Uuid::new_v4());
+        }
+        if let Some(relationship) = &mut personality.relationship {
+            relationship.id = Some(Uuid::new_v4());
+            for boundary in &mut relationship.boundaries {
+                boundary.id = Some(Uuid::new_v4());
+            }
+        }
+        soul
     }
 
-    /// Create a `Soul`.
-    pub async fn create(personality: &Soul, soul: Soulgraph) -> Result<Soul, CorruptSoul> {
-        if let Ok(response) = soul.post("/personality", personality).await {
-            match response.status() {
-                reqwest::StatusCode::OK => match response.json::<Soul>().await {
-                    Ok(p) => Ok(p),
-                    Err(_) => Err(CorruptSoul),
-                },
-                _ => Err(CorruptSoul),
+    /// Top-level fields the server sent that this crate does not model.
+    pub fn extra(&self) -> &HashMap<String, serde_json::Value> {
+        &self.extra
+    }
+
+    /// Stable SHA-256 hex digest of the soul's content.
+    ///
+    /// Ids and the personality's `creation_date`/`last_modified` metadata are excluded, and
+    /// object keys are hashed in sorted order, so semantically equal souls always match.
+    pub fn fingerprint(&self) -> String {
+        let mut value = serde_json::to_value(self).expect("Soul always serializes to JSON");
+        strip_ids(&mut value);
+        if let Some(metadata) = value
+            .pointer_mut("/personality/metadata")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            for key in VOLATILE_METADATA {
+                metadata.remove(key);
             }
-        } else {
-            Err(CorruptSoul)
         }
+
+        Sha256::digest(value.to_string().as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
     }
 
-    /// Delete a `Soul` stored under `id`.
-    pub async fn delete(id: &str, soul: Soulgraph) -> Result<(), CorruptSoul> {
-        if let Ok(response) = soul.delete(format!("/personality/{id}").as_str()).await {
-            match response.status() {
-                reqwest::StatusCode::OK => Ok(()),
-                _ => Err(CorruptSoul),
+    /// Non-fatal advisories about the soul's definition, in personality order.
+    ///
+    /// Unlike builder validation these never reject a soul; they point at fields that are
+    /// likely mistakes or could be fleshed out.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let personality = &self.personality;
+        let mut warnings = Vec::new();
+
+        for trait_ in &personality.traits {
+            if trait_.strength < WEAK_TRAIT_STRENGTH {
+                warnings.push(LintWarning::WeakTrait {
+                    name: trait_.r#trait.clone(),
+                    strength: trait_.strength,
+                });
+            }
+            if trait_.expression_rules.as_ref().is_some_and(Vec::is_empty) {
+                warnings.push(LintWarning::EmptyExpressionRules(trait_.r#trait.clone()));
+            }
+        }
+
+        if personality.values().is_empty() {
+            warnings.push(LintWarning::NoValues);
+        }
+        for value in personality.values() {
+            if value.expression.trim().is_empty() {
+                warnings.push(LintWarning::EmptyValueExpression(value.name.clone()));
+            }
+        }
+
+        if let Some(voice) = &personality.voice {
+            if voice.qualities.len() < MIN_VOICE_QUALITIES {
+                warnings.push(LintWarning::SparseVoiceQualities(voice.qualities.len()));
             }
+        }
+
+        warnings
+    }
+
+    /// Counts of the soul's traits, values and boundaries, with its mean trait strength.
+    pub fn summary(&self) -> SoulSummary {
+        let personality = &self.personality;
+        let traits = &personality.traits;
+        SoulSummary {
+            trait_count: traits.len(),
+            value_count: personality.values().len(),
+            has_voice: personality.voice.is_some(),
+            boundary_count: personality
+                .relationship
+                .as_ref()
+                .map_or(0, |relationship| relationship.boundaries.len()),
+            average_trait_strength: (!traits.is_empty()).then(|| {
+                traits.iter().map(|trait_| trait_.strength).sum::<f32>() / traits.len() as f32
+            }),
+        }
+    }
+
+    /// A `0..=1` heuristic of how coherent the soul is, using
+    /// [`DEFAULT_TONE_CONTRADICTIONS`]; see [`Soul::consistency_score_with`].
+    pub fn consistency_score(&self) -> f32 {
+        self.consistency_score_with(DEFAULT_TONE_CONTRADICTIONS)
+    }
+
+    /// A `0..=1` heuristic of how coherent the soul is, where `1.0` means nothing was
+    /// penalized. Three penalties are subtracted from `1.0`, each the share of its items
+    /// that are inconsistent:
+    ///
+    /// - value conflicts with a blank `resolution`, out of all value conflicts (weight 0.4);
+    /// - traits without expression rules, out of all traits (weight 0.3);
+    /// - dominant traits (strength at least 0.7) whose voice tone contains a contradicting
+    ///   word from `tone_contradictions`, out of all dominant traits (weight 0.3).
+    ///
+    /// `tone_contradictions` holds `(trait, tone word)` pairs, both matched
+    /// case-insensitively. A penalty with nothing to count is zero.
+    pub fn consistency_score_with(&self, tone_contradictions: &[(&str, &str)]) -> f32 {
+        let personality = &self.personality;
+        let share = |bad: usize, total: usize| {
+            if total == 0 {
+                0.0
+            } else {
+                bad as f32 / total as f32
+            }
+        };
+
+        let conflicts: Vec<_> = personality
+            .values()
+            .iter()
+            .flat_map(|value| value.conflicts.iter().flatten())
+            .collect();
+        let unresolved = conflicts
+            .iter()
+            .filter(|conflict| conflict.resolution.trim().is_empty())
+            .count();
+
+        let traits = &personality.traits;
+        let without_rules = traits
+            .iter()
+            .filter(|trait_| trait_.expression_rules.as_ref().is_none_or(Vec::is_empty))
+            .count();
+
+        let tone = personality
+            .voice
+            .as_ref()
+            .map(|voice| voice.tone.to_lowercase())
+            .unwrap_or_default();
+        let dominant: Vec<_> = traits
+            .iter()
+            .filter(|trait_| trait_.strength >= DOMINANT_TRAIT_STRENGTH)
+            .collect();
+        let contradicted = dominant
+            .iter()
+            .filter(|trait_| {
+                tone_contradictions.iter().any(|(name, word)| {
+                    trait_.r#trait.eq_ignore_ascii_case(name) && tone.contains(&word.to_lowercase())
+                })
+            })
+            .count();
+
+        let penalty = UNRESOLVED_CONFLICT_WEIGHT * share(unresolved, conflicts.len())
+            + EMPTY_RULES_WEIGHT * share(without_rules, traits.len())
+            + TONE_CONTRADICTION_WEIGHT * share(contradicted, dominant.len());
+        (1.0 - penalty).clamp(0.0, 1.0)
+    }
+
+    /// Renders the soul as a system-prompt string: one identity line naming the personality
+    /// and describing the entity, followed by the rest of the personality block.
+    pub fn to_prompt(&self) -> String {
+        let name = self.personality.name.trim();
+        let identity = if name.is_empty() {
+            self.entity.to_prompt()
         } else {
-            Err(CorruptSoul)
+            format!("You are {name}, {}", self.entity.description())
+        };
+        let mut sections = vec![identity];
+        sections.extend(self.personality.prompt_sections());
+        sections.join("\n\n")
+    }
+
+    /// Write the soul to `path` as pretty-printed JSON.
+    pub fn to_file(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a soul previously written with [`Soul::to_file`].
+    pub fn from_file(path: &Path) -> Result<Soul, SoulgraphError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Get the `Soul` with the given `id`.
+    pub async fn get(id: &str, soul: &Soulgraph) -> Result<Soul, SoulgraphError> {
+        soul.get_cached(&format!("/personality/{id}")).await
+    }
+
+    /// Fetch only the given dotted `fields` of the `Soul` with `id`, e.g.
+    /// `["entity", "personality.name"]`, as the raw JSON projection the server returns.
+    pub async fn get_fields(
+        id: &str,
+        fields: &[&str],
+        soul: &Soulgraph,
+    ) -> Result<serde_json::Value, SoulgraphError> {
+        if fields.is_empty() {
+            return Err(SoulgraphError::InvalidInput(
+                "fields must not be empty".to_string(),
+            ));
         }
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| url::form_urlencoded::byte_serialize(field.as_bytes()).collect())
+            .collect();
+        let response = soul
+            .get(format!("/personality/{id}?fields={}", fields.join(",")))
+            .await?;
+        response::decode(response).await
+    }
+
+    /// Whether a `Soul` is stored under `id`, checked without fetching it.
+    pub async fn exists(id: &str, soul: &Soulgraph) -> Result<bool, SoulgraphError> {
+        let response = soul.head(format!("/soul/{id}")).await?;
+        response::exists(response).await
+    }
+
+    /// Get the `Soul`s with the given `ids`, running at most `concurrency` requests at once.
+    ///
+    /// Results are returned in the order of `ids`; `concurrency` is clamped to `1..=32`.
+    pub async fn get_many(
+        ids: &[&str],
+        soul: &Soulgraph,
+        concurrency: usize,
+    ) -> Vec<Result<Soul, SoulgraphError>> {
+        let mut results: Vec<_> = stream::iter(ids.iter().enumerate())
+            .map(|(index, id)| async move { (index, Soul::get(id, soul).await) })
+            .buffer_unordered(concurrency.clamp(1, MAX_CONCURRENCY))
+            .collect()
+            .await;
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Create a `Soul`.
+    pub async fn create(personality: &Soul, soul: &Soulgraph) -> Result<Soul, SoulgraphError> {
+        let response = soul.post("/personality", personality).await?;
+        response::decode(response).await
+    }
+
+    /// Delete a `Soul` stored under `id`.
+    pub async fn delete(id: &str, soul: &Soulgraph) -> Result<DeleteResult, SoulgraphError> {
+        let response = soul.delete(format!("/personality/{id}")).await?;
+        response::delete_result(response, id).await
+    }
+}
+
+fn strip_ids(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.remove("id");
+            map.values_mut().for_each(strip_ids);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_ids),
+        _ => {}
+    }
+}
+
+impl TryFrom<serde_json::Value> for Soul {
+    type Error = SoulgraphError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(serde_json::from_value(value)?)
     }
 }
 
@@ -99,27 +494,32 @@ impl SoulBuilder {
         Self::default()
     }
 
+    #[must_use]
     pub fn version(mut self, version: String) -> Self {
         self.version = Some(version);
         self
     }
 
+    #[must_use]
     pub fn entity(mut self, entity: entity::Entity) -> Self {
         self.entity = Some(entity);
         self
     }
 
+    #[must_use]
     pub fn personality(mut self, personality: personality::Personality) -> Self {
         self.personality = Some(personality);
         self
     }
 
+    #[must_use]
     pub fn build(self) -> Soul {
         Soul {
             id: None,
             version: self.version.unwrap_or_else(|| DEFAULT_VERSION.to_string()),
             entity: self.entity.unwrap_or_default(),
             personality: self.personality.unwrap_or_default(),
+            extra: HashMap::new(),
         }
     }
 }
@@ -131,6 +531,140 @@ mod tests {
     use personality::Personality;
     use serde_json::json;
     use std::collections::HashMap;
+    use tempfile::tempdir;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn well_formed_personality() -> Personality {
+        Personality {
+            values: Some(vec![personality::value::Value {
+                name: "honesty".to_string(),
+                importance: 0.9,
+                expression: "tells the truth".to_string(),
+                ..Default::default()
+            }]),
+            voice: Some(personality::voice::Voice::default()),
+            ..Personality::default()
+        }
+    }
+
+    #[test]
+    fn test_lint_well_formed_soul() {
+        let soul = Soul::builder()
+            .personality(well_formed_personality())
+            .build();
+        assert!(soul.lint().is_empty());
+    }
+
+    #[test]
+    fn test_lint_reports_each_warning() {
+        let mut personality = well_formed_personality();
+        personality.traits[0].strength = 0.05;
+        personality.traits[1].expression_rules = Some(Vec::new());
+        personality.values.as_mut().unwrap()[0].expression = "  ".to_string();
+        personality.voice.as_mut().unwrap().qualities.truncate(1);
+        let soul = Soul::builder().personality(personality).build();
+
+        assert_eq!(
+            soul.lint(),
+            vec![
+                LintWarning::WeakTrait {
+                    name: "helpful".to_string(),
+                    strength: 0.05,
+                },
+                LintWarning::EmptyExpressionRules("professional".to_string()),
+                LintWarning::EmptyValueExpression("honesty".to_string()),
+                LintWarning::SparseVoiceQualities(1),
+            ]
+        );
+
+        assert_eq!(Soul::default().lint(), vec![LintWarning::NoValues]);
+        assert_eq!(
+            LintWarning::SparseVoiceQualities(1).to_string(),
+            "voice has 1 qualities; at least 2 are recommended"
+        );
+    }
+
+    #[test]
+    fn test_to_prompt_combines_entity_and_personality() {
+        assert_eq!(
+            Soul::default().to_prompt(),
+            "You are AI Assistant, an AI assistant.\n\
+             \n\
+             Traits:\n\
+             - helpful (strength 0.90): always seeks to assist\n\
+             - professional (strength 0.80): maintains appropriate boundaries"
+        );
+
+        let unnamed = Soul {
+            personality: Personality {
+                name: String::new(),
+                traits: Vec::new(),
+                ..Personality::default()
+            },
+            ..Soul::default()
+        };
+        assert_eq!(unnamed.to_prompt(), "You are an AI assistant.");
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let mut soul = Soul::builder()
+            .version("2.0".to_string())
+            .entity(
+                entity::EntityBuilder::new()
+                    .form("human")
+                    .occupation("trader")
+                    .background("former floor trader")
+                    .expertise(vec!["markets".to_string()])
+                    .build()
+                    .unwrap(),
+            )
+            .build();
+        soul.id = Some(Uuid::new_v4());
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("soul.json");
+
+        soul.to_file(&path).unwrap();
+        assert_eq!(Soul::from_file(&path).unwrap(), soul);
+    }
+
+    #[test]
+    fn test_from_file_errors() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.json");
+        assert!(matches!(
+            Soul::from_file(&missing),
+            Err(SoulgraphError::Io(_))
+        ));
+
+        let invalid = dir.path().join("invalid.json");
+        fs::write(&invalid, "not json").unwrap();
+        assert!(matches!(
+            Soul::from_file(&invalid),
+            Err(SoulgraphError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let mut value = serde_json::to_value(Soul::default()).unwrap();
+        value["custom_field"] = json!({ "owner": "team-a", "tier": 2 });
+
+        let mut soul: Soul = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            soul.extra().get("custom_field"),
+            Some(&json!({ "owner": "team-a", "tier": 2 }))
+        );
+
+        soul.version = "2.0".to_string();
+        let updated = serde_json::to_value(&soul).unwrap();
+        assert_eq!(updated["version"], "2.0");
+        assert_eq!(updated["custom_field"]["owner"], "team-a");
+        assert!(Soul::default().extra().is_empty());
+    }
 
     #[test]
     fn test_soulscript_serialization() {
@@ -139,6 +673,7 @@ mod tests {
             version: "1.0".to_string(),
             entity: entity::Entity::default(),
             personality: personality::Personality::default(),
+            extra: HashMap::new(),
         };
 
         let serialized = serde_json::to_value(&script).unwrap();
@@ -146,9 +681,8 @@ mod tests {
         assert_eq!(serialized["entity"]["form"], "ai");
     }
 
-    #[test]
-    fn test_soulscript_deserialization() {
-        let json = json!({
+    fn sample_soul_json() -> serde_json::Value {
+        json!({
         "id": "550e8400-e29b-41d4-a716-446655440000",
         "version": "1.0.0",
         "entity": {
@@ -253,13 +787,407 @@ mod tests {
                 "last_modified": "2025-01-11"
             }
         }
-        });
+        })
+    }
+
+    #[test]
+    fn test_soulscript_deserialization() {
+        let json = sample_soul_json();
 
         let script: Soul = serde_json::from_value(json).unwrap();
         assert_eq!(script.version, "1.0.0");
         assert_eq!(script.entity.form, "human");
     }
 
+    #[test]
+    fn test_consistency_score() {
+        let clean: Soul = serde_json::from_value(sample_soul_json()).unwrap();
+        assert_eq!(clean.consistency_score(), 1.0);
+
+        let mut inconsistent = clean.clone();
+        let personality = &mut inconsistent.personality;
+        personality.traits[0].expression_rules = None;
+        personality.traits[1].expression_rules = Some(Vec::new());
+        personality.voice.as_mut().unwrap().tone = "Earnest and gentle".to_string();
+        personality.values.as_mut().unwrap()[0].conflicts = Some(vec![
+            personality::value::ValueConflict {
+                id: None,
+                value: "brutal honesty".to_string(),
+                resolution: " ".to_string(),
+            },
+            personality::value::ValueConflict {
+                id: None,
+                value: "emotional hardening".to_string(),
+                resolution: "honesty wins".to_string(),
+            },
+        ]);
+
+        // Half the conflicts unresolved, half the traits without rules and two of the four
+        // dominant traits (sarcastic, tough-love) contradicted by the tone.
+        let score = inconsistent.consistency_score();
+        approx::assert_relative_eq!(score, 1.0 - 0.4 * 0.5 - 0.3 * 0.5 - 0.3 * 0.5);
+        assert!(score < clean.consistency_score());
+
+        approx::assert_relative_eq!(
+            inconsistent.consistency_score_with(&[]),
+            1.0 - 0.4 * 0.5 - 0.3 * 0.5
+        );
+    }
+
+    #[test]
+    fn test_summary_of_sample_soul() {
+        let soul: Soul = serde_json::from_value(sample_soul_json()).unwrap();
+        let summary = soul.summary();
+        assert_eq!(summary.trait_count, 4);
+        assert_eq!(summary.value_count, 3);
+        assert!(summary.has_voice);
+        assert_eq!(summary.boundary_count, 1);
+        approx::assert_relative_eq!(summary.average_trait_strength.unwrap(), 0.875);
+    }
+
+    #[test]
+    fn test_summary_of_default_soul() {
+        let soul = Soul::default();
+        let summary = soul.summary();
+        assert_eq!(summary.trait_count, 2);
+        assert_eq!(summary.value_count, 0);
+        assert!(!summary.has_voice);
+        assert_eq!(summary.boundary_count, 0);
+        approx::assert_relative_eq!(summary.average_trait_strength.unwrap(), 0.85);
+
+        let empty = Soul {
+            personality: Personality {
+                traits: Vec::new(),
+                ..soul.personality
+            },
+            ..soul
+        };
+        assert_eq!(empty.summary().average_trait_strength, None);
+    }
+
+    #[test]
+    fn test_soul_try_from_value() {
+        let soul = Soul::try_from(json!({
+            "version": "1.0",
+            "entity": { "form": "ai", "occupation": "assistant" },
+            "personality": { "name": "Test", "traits": [] }
+        }))
+        .unwrap();
+        assert_eq!(soul.personality.name, "Test");
+
+        let result = Soul::try_from(json!({ "version": 1 }));
+        assert!(matches!(result, Err(SoulgraphError::Decode(_))));
+    }
+
+    #[tokio::test]
+    async fn test_soul_get_many_preserves_order() {
+        let server = MockServer::start().await;
+        let ids = ["soul-a", "soul-b", "soul-c"];
+        for (delay, id) in [(60, ids[0]), (0, ids[1]), (30, ids[2])] {
+            let body = serde_json::to_value(Soul {
+                version: id.to_string(),
+                ..Soul::default()
+            })
+            .unwrap();
+            Mock::given(method("GET"))
+                .and(path(format!("/personality/{id}")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(body)
+                        .set_delay(std::time::Duration::from_millis(delay)),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/personality/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let results = Soul::get_many(&[ids[0], ids[1], "missing", ids[2]], &soulgraph, 4).await;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().version, "soul-a");
+        assert_eq!(results[1].as_ref().unwrap().version, "soul-b");
+        assert!(matches!(results[2], Err(SoulgraphError::Status { .. })));
+        assert_eq!(results[3].as_ref().unwrap().version, "soul-c");
+    }
+
+    #[tokio::test]
+    async fn test_soul_get_many_empty() {
+        let soulgraph = Soulgraph::new("test-key", "http://127.0.0.1:0");
+        let results = Soul::get_many(&[], &soulgraph, 0).await;
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_soul_delete_parses_confirmation() {
+        let server = MockServer::start().await;
+        let id = "550e8400-e29b-41d4-a716-446655440000";
+        Mock::given(method("DELETE"))
+            .and(path(format!("/personality/{id}")))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "id": id, "deleted": false })),
+            )
+            .mount(&server)
+            .await;
+
+        let result = Soul::delete(id, &Soulgraph::new("test-key", &server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(result.id.to_string(), id);
+        assert!(!result.deleted);
+    }
+
+    #[tokio::test]
+    async fn test_soul_delete_accepts_empty_no_content() {
+        let server = MockServer::start().await;
+        let id = "550e8400-e29b-41d4-a716-446655440000";
+        Mock::given(method("DELETE"))
+            .and(path(format!("/personality/{id}")))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let result = Soul::delete(id, &Soulgraph::new("test-key", &server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(result.id.to_string(), id);
+        assert!(result.deleted);
+    }
+
+    #[tokio::test]
+    async fn test_soul_delete_surfaces_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let result = Soul::delete(
+            "550e8400-e29b-41d4-a716-446655440000",
+            &Soulgraph::new("test-key", &server.uri()),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SoulgraphError::Status { status, .. }) if status == reqwest::StatusCode::NOT_FOUND
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_ids_ordering_and_timestamps() {
+        let first: Soul = serde_json::from_str(
+            r#"{
+                "id": "550e8400-e29b-41d4-a716-446655440000",
+                "version": "1.0",
+                "entity": { "form": "ai", "occupation": "assistant" },
+                "personality": {
+                    "name": "Test",
+                    "traits": [{ "trait": "kind", "strength": 0.5 }],
+                    "metadata": { "creation_date": "2025-01-11", "author": "luna" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let second: Soul = serde_json::from_str(
+            r#"{
+                "personality": {
+                    "metadata": { "author": "luna", "creation_date": "2025-02-01" },
+                    "traits": [{ "strength": 0.5, "trait": "kind", "id": "67e55044-10b1-426f-9247-bb680e5fe0c8" }],
+                    "name": "Test"
+                },
+                "entity": { "occupation": "assistant", "form": "ai" },
+                "version": "1.0"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(first.fingerprint().len(), 64);
+    }
+
+    #[test]
+    fn test_instantiate_assigns_fresh_ids() {
+        let template: Soul = serde_json::from_value(json!({
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "version": "1.0",
+            "entity": { "id": "550e8400-e29b-41d4-a716-446655440001", "form": "ai", "occupation": "assistant" },
+            "personality": {
+                "name": "Test",
+                "traits": [{ "trait": "kind", "strength": 0.5 }],
+                "values": [{
+                    "name": "honesty",
+                    "importance": 0.9,
+                    "expression": "tells the truth",
+                    "conflicts": [{ "value": "tact", "resolution": "balance" }]
+                }],
+                "voice": { "style": "warm", "tone": "friendly", "qualities": [], "patterns": [] },
+                "relationship": {
+                    "style": "mentor",
+                    "boundaries": [{ "type": "privacy", "description": "no secrets", "enforcement": "strict" }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let first = template.instantiate();
+        let second = template.instantiate();
+        assert_eq!(first.fingerprint(), template.fingerprint());
+
+        let ids = |soul: &Soul| {
+            let personality = &soul.personality;
+            let value = &personality.values()[0];
+            let relationship = personality.relationship.as_ref().unwrap();
+            vec![
+                soul.id,
+                soul.entity.id,
+                personality.id,
+                personality.traits[0].id,
+                value.id,
+                value.conflicts.as_ref().unwrap()[0].id,
+                personality.voice.as_ref().unwrap().id,
+                relationship.id,
+                relationship.boundaries[0].id,
+            ]
+        };
+        for (index, ((new, other), old)) in ids(&first)
+            .into_iter()
+            .zip(ids(&second))
+            .zip(ids(&template))
+            .enumerate()
+        {
+            assert!(new.is_some(), "id {index} was not assigned");
+            assert_ne!(new, old, "id {index} kept the template's value");
+            assert_ne!(new, other, "id {index} is shared between instances");
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_trait_strength() {
+        let soul = Soul::default();
+        let mut changed = soul.clone();
+        changed.personality.traits[0].strength = 0.1;
+
+        assert_ne!(soul.fingerprint(), changed.fingerprint());
+    }
+
+    #[test]
+    fn test_migrate_from_1_0() {
+        let mut soul = Soul {
+            version: "1.0.0".to_string(),
+            ..Soul::default()
+        };
+        soul.personality.metadata = None;
+        let expected = Soul {
+            version: "1.0".to_string(),
+            ..soul.clone()
+        };
+
+        soul.migrate().unwrap();
+
+        assert_eq!(soul.version, "1.0");
+        assert_eq!(soul, expected);
+    }
+
+    #[test]
+    fn test_migrate_normalizes_version_strings() {
+        for version in ["1.0.0", "v1", "v1.0", "1.0"] {
+            let mut soul = Soul {
+                version: version.to_string(),
+                ..Soul::default()
+            };
+            soul.migrate().unwrap();
+            assert_eq!(soul.version, DEFAULT_VERSION);
+        }
+    }
+
+    #[test]
+    fn test_migrate_rejects_unknown_versions() {
+        for (version, expected) in [
+            (
+                "latest",
+                MigrationError::InvalidVersion("latest".to_string()),
+            ),
+            ("0.9", MigrationError::UnsupportedVersion("0.9".to_string())),
+            ("1.1", MigrationError::UnsupportedVersion("1.1".to_string())),
+            ("2.0", MigrationError::UnsupportedVersion("2.0".to_string())),
+        ] {
+            let mut soul = Soul {
+                version: version.to_string(),
+                ..Soul::default()
+            };
+            assert_eq!(soul.migrate(), Err(expected));
+            assert_eq!(soul.version, version);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_soul_get_fields() {
+        let server = MockServer::start().await;
+        let projection = json!({
+            "entity": { "form": "human" },
+            "personality": { "name": "Sage" }
+        });
+        Mock::given(method("GET"))
+            .and(path("/personality/abc"))
+            .and(query_param("fields", "entity,personality.name"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&projection))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        let fields = Soul::get_fields("abc", &["entity", "personality.name"], &soulgraph)
+            .await
+            .unwrap();
+        assert_eq!(fields, projection);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].url.query(),
+            Some("fields=entity,personality.name")
+        );
+
+        assert!(matches!(
+            Soul::get_fields("abc", &[], &soulgraph).await,
+            Err(SoulgraphError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_soul_exists() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/soul/present"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/soul/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/soul/broken"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let soulgraph = Soulgraph::new("test-key", &server.uri());
+        assert!(Soul::exists("present", &soulgraph).await.unwrap());
+        assert!(!Soul::exists("missing", &soulgraph).await.unwrap());
+        assert!(matches!(
+            Soul::exists("broken", &soulgraph).await,
+            Err(SoulgraphError::Status { .. })
+        ));
+    }
+
     #[test]
     fn test_soul_default() {
         let soul = Soul::default();
@@ -0,0 +1,110 @@
+//! Object-safe access to soul storage, for code that needs a `dyn` store.
+
+use futures::future::BoxFuture;
+
+use crate::{DeleteResult, Soul, Soulgraph, SoulgraphError};
+
+/// Soul CRUD operations returning boxed futures, so implementors can be used as
+/// `Box<dyn DynSoulStore>`, e.g. to swap the HTTP client for a local store in a plugin.
+pub trait DynSoulStore: Send + Sync {
+    /// Get the `Soul` with the given `id`.
+    fn get<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Soul, SoulgraphError>>;
+
+    /// Create a `Soul`.
+    fn create<'a>(&'a self, soul: &'a Soul) -> BoxFuture<'a, Result<Soul, SoulgraphError>>;
+
+    /// Delete a `Soul` stored under `id`.
+    fn delete<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<DeleteResult, SoulgraphError>>;
+}
+
+impl DynSoulStore for Soulgraph {
+    fn get<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Soul, SoulgraphError>> {
+        Box::pin(Soul::get(id, self))
+    }
+
+    fn create<'a>(&'a self, soul: &'a Soul) -> BoxFuture<'a, Result<Soul, SoulgraphError>> {
+        Box::pin(Soul::create(soul, self))
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<DeleteResult, SoulgraphError>> {
+        Box::pin(Soul::delete(id, self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, sync::Mutex};
+    use uuid::Uuid;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Default)]
+    struct InMemoryStore {
+        souls: Mutex<HashMap<String, Soul>>,
+    }
+
+    impl DynSoulStore for InMemoryStore {
+        fn get<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Soul, SoulgraphError>> {
+            let soul = self.souls.lock().unwrap().get(id).cloned();
+            Box::pin(async move {
+                soul.ok_or_else(|| SoulgraphError::InvalidInput(format!("no soul {id}")))
+            })
+        }
+
+        fn create<'a>(&'a self, soul: &'a Soul) -> BoxFuture<'a, Result<Soul, SoulgraphError>> {
+            let mut soul = soul.clone();
+            let id = *soul.id.get_or_insert_with(Uuid::new_v4);
+            self.souls
+                .lock()
+                .unwrap()
+                .insert(id.to_string(), soul.clone());
+            Box::pin(async move { Ok(soul) })
+        }
+
+        fn delete<'a>(
+            &'a self,
+            id: &'a str,
+        ) -> BoxFuture<'a, Result<DeleteResult, SoulgraphError>> {
+            let deleted = self.souls.lock().unwrap().remove(id).is_some();
+            Box::pin(async move {
+                Ok(DeleteResult {
+                    id: Uuid::parse_str(id)?,
+                    deleted,
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_through_dyn_stores() {
+        let remote_soul = Soul::default();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/personality/remote"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&remote_soul))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let local = InMemoryStore::default();
+        let local_soul = local.create(&Soul::default()).await.unwrap();
+        let local_id = local_soul.id.unwrap().to_string();
+
+        let stores: Vec<(Box<dyn DynSoulStore>, &str)> = vec![
+            (
+                Box::new(Soulgraph::new("test-key", &server.uri())),
+                "remote",
+            ),
+            (Box::new(local), &local_id),
+        ];
+        let mut fetched = Vec::new();
+        for (store, id) in &stores {
+            fetched.push(store.get(id).await.unwrap());
+        }
+
+        assert_eq!(fetched, vec![remote_soul, local_soul]);
+    }
+}
@@ -0,0 +1,67 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Prefix some senders put before the hex digest in the signature header.
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Checks that `signature_header` is the hex HMAC-SHA256 of `body` keyed with `secret`.
+///
+/// The header may carry a `sha256=` prefix. Digests are compared in constant time.
+pub fn verify_webhook_signature(secret: &[u8], body: &[u8], signature_header: &str) -> bool {
+    let hex = signature_header.trim();
+    let hex = hex.strip_prefix(SIGNATURE_PREFIX).unwrap_or(hex);
+    let Some(signature) = decode_hex(hex) else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"It's a Secret to Everybody";
+    const BODY: &[u8] = b"Hello, World!";
+    const SIGNATURE: &str = "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    #[test]
+    fn test_valid_signature() {
+        assert!(verify_webhook_signature(SECRET, BODY, SIGNATURE));
+        assert!(verify_webhook_signature(
+            SECRET,
+            BODY,
+            &format!("sha256={SIGNATURE}")
+        ));
+        assert!(verify_webhook_signature(
+            SECRET,
+            BODY,
+            &SIGNATURE.to_uppercase()
+        ));
+    }
+
+    #[test]
+    fn test_rejects_tampered_or_malformed() {
+        assert!(!verify_webhook_signature(
+            SECRET,
+            b"Hello, World?",
+            SIGNATURE
+        ));
+        assert!(!verify_webhook_signature(b"wrong secret", BODY, SIGNATURE));
+        assert!(!verify_webhook_signature(SECRET, BODY, &SIGNATURE[..62]));
+        assert!(!verify_webhook_signature(SECRET, BODY, "not hex"));
+        assert!(!verify_webhook_signature(SECRET, BODY, ""));
+    }
+}